@@ -78,3 +78,168 @@ macro_rules! dump_params {
     }
 }
 pub(crate) use dump_params;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Environment variable with the directory to which the code generated for items with `dump` is
+/// also written, one file per item and version: `<item>.<mode>.<hash>.rs`.
+pub const DUMP_DIR_ENV_NAME: &str = "MAYBE_ASYNC_CFG_DUMP_DIR";
+
+/// Returns an item reporting the code generated for a version of an item (`dump` parameter) as a
+/// warning, preceded by a summary of each conversion report. Warnings of proc macros are shown by
+/// nightly compilers only, so the warning is the deprecation note of an item used right away. The
+/// code is also written to a file if `MAYBE_ASYNC_CFG_DUMP_DIR` is set; the hash of the code in
+/// the file name keeps apart the items of the same name from different modules.
+pub fn dump_converted(
+    item: &str,
+    mode: &str,
+    reports: &[ConversionReport],
+    ts: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    use std::hash::{Hash, Hasher};
+
+    let mut code = String::new();
+    for report in reports {
+        code.push_str(&format!("// {}\n", report.summary()));
+    }
+    code.push_str(&ts.to_string());
+
+    let mut note = format!("maybe_async_cfg: code of `{}` ({} version)", item, mode);
+    if let Some(dir) = std::env::var_os(DUMP_DIR_ENV_NAME) {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        std::env::var("CARGO_CRATE_NAME").ok().hash(&mut hasher);
+        code.hash(&mut hasher);
+
+        let path = std::path::Path::new(&dir).join(format!("{}.{}.{:016x}.rs", item, mode, hasher.finish()));
+        match std::fs::write(&path, &code) {
+            Ok(_) => note.push_str(&format!(", written to {}", path.display())),
+            Err(err) => note.push_str(&format!(", can't write {}: {}", path.display(), err)),
+        }
+    }
+    note.push_str(":\n");
+    note.push_str(&code);
+
+    quote::quote! {
+        const _: () = {
+            #[deprecated(note = #note)]
+            struct MaybeAsyncCfgDump;
+            let _ = MaybeAsyncCfgDump;
+        };
+    }
+}
+
+/// Environment variable with the path of the file to which conversion reports are appended.
+pub const REPORT_ENV_NAME: &str = "MAYBE_ASYNC_CFG_REPORT";
+
+/// Report of one conversion (one version of one item), written as a single line of JSON.
+///
+/// JSON schema of the line:
+///
+/// ```json
+/// {
+///     "$schema": "http://json-schema.org/draft-07/schema#",
+///     "type": "object",
+///     "required": ["item", "mode", "key", "renames", "awaits_stripped", "attrs_dropped",
+///                  "features_replaced"],
+///     "properties": {
+///         "item": { "type": "string" },
+///         "mode": { "enum": ["sync", "async"] },
+///         "key": { "type": ["string", "null"] },
+///         "renames": {
+///             "type": "array",
+///             "items": {
+///                 "type": "object",
+///                 "required": ["from", "to"],
+///                 "properties": { "from": { "type": "string" }, "to": { "type": "string" } }
+///             }
+///         },
+///         "awaits_stripped": { "type": "integer", "minimum": 0 },
+///         "attrs_dropped": { "type": "array", "items": { "type": "string" } },
+///         "features_replaced": {
+///             "type": "array",
+///             "items": {
+///                 "type": "object",
+///                 "required": ["from", "to"],
+///                 "properties": { "from": { "type": "string" }, "to": { "type": "string" } }
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct ConversionReport {
+    pub renames: Vec<(String, String)>,
+    pub awaits_stripped: usize,
+    pub attrs_dropped: Vec<String>,
+    pub features_replaced: Vec<(String, String)>,
+}
+
+impl ConversionReport {
+    /// Returns an empty report if reports are enabled by the environment variable.
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(REPORT_ENV_NAME).map(|_| Self::default())
+    }
+
+    /// Counts of the report, e.g. `renames: 2, awaits stripped: 1, attributes dropped: 0,
+    /// features replaced: 0`.
+    pub fn summary(&self) -> String {
+        format!(
+            "renames: {}, awaits stripped: {}, attributes dropped: {}, features replaced: {}",
+            self.renames.len(),
+            self.awaits_stripped,
+            self.attrs_dropped.len(),
+            self.features_replaced.len(),
+        )
+    }
+
+    pub fn to_json(&self, item: &str, mode: &str, key: Option<&str>) -> String {
+        let pairs = |list: &Vec<(String, String)>| {
+            list.iter()
+                .map(|(from, to)| format!("{{\"from\":{},\"to\":{}}}", json_str(from), json_str(to)))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        format!(
+            "{{\"item\":{},\"mode\":{},\"key\":{},\"renames\":[{}],\"awaits_stripped\":{},\"attrs_dropped\":[{}],\"features_replaced\":[{}]}}",
+            json_str(item),
+            json_str(mode),
+            key.map(json_str).unwrap_or_else(|| "null".to_string()),
+            pairs(&self.renames),
+            self.awaits_stripped,
+            self.attrs_dropped.iter().map(|s| json_str(s)).collect::<Vec<_>>().join(","),
+            pairs(&self.features_replaced),
+        )
+    }
+
+    /// Appends the report to the file named by the environment variable. Errors are ignored: the
+    /// report must never affect the compilation.
+    pub fn write(&self, item: &str, mode: &str, key: Option<&str>) {
+        use std::io::Write;
+
+        if let Some(path) = std::env::var_os(REPORT_ENV_NAME) {
+            let line = self.to_json(item, mode, key);
+            if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
+                let _ = writeln!(file, "{}", line);
+            }
+        }
+    }
+}
+
+fn json_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -24,6 +24,9 @@
 //! - trait implementation
 //! - function definition
 //! - struct and enum definition
+//! - const and static items
+//! - type alias
+//! - `macro_rules!` definition
 //!
 //! **RECOMMENDATION**: Enable **resolver ver2** in your crate, which is introduced in Rust 1.51. If
 //! not, two crates in dependency with conflict version (one async and another blocking) can fail
@@ -151,6 +154,18 @@
 //!     ```
 //!     
 //!
+//! ## Conversion report
+//!
+//! Set the environment variable `MAYBE_ASYNC_CFG_REPORT` to a file path to get a report of all
+//! conversions made during the build. One line of JSON is appended to the file for every version
+//! of every item: the item name, the mode (`sync` or `async`), the version key, the renames applied,
+//! the number of awaits stripped, the attributes dropped and the features replaced. Without the
+//! variable nothing is collected or written, and the generated code does not depend on it.
+//!
+//! ```text
+//! {"item":"func","mode":"sync","key":"sync","renames":[{"from":"func","to":"func_sync"}],"awaits_stripped":1,"attrs_dropped":[],"features_replaced":[]}
+//! ```
+//!
 //! ## Examples
 //!
 //! ### rust client for services
@@ -190,21 +205,50 @@ mod visitor_content;
 
 mod debug;
 
-const DEFAULT_CRATE_NAME: &'static str = "maybe_async_cfg";
-const MACRO_MAYBE_NAME: &'static str = "maybe";
-const MACRO_ONLY_IF_NAME: &'static str = "only_if";
-const MACRO_REMOVE_IF_NAME: &'static str = "remove_if";
-const MACRO_NOOP_NAME: &'static str = "noop";
-const MACRO_REMOVE_NAME: &'static str = "remove";
-const MACRO_DEFAULT_NAME: &'static str = "default";
+const DEFAULT_CRATE_NAME: &str = "maybe_async_cfg";
+const MACRO_MAYBE_NAME: &str = "maybe";
+const MACRO_ONLY_IF_NAME: &str = "only_if";
+const MACRO_REMOVE_IF_NAME: &str = "remove_if";
+const MACRO_NOOP_NAME: &str = "noop";
+const MACRO_REMOVE_NAME: &str = "remove";
+const MACRO_DEFAULT_NAME: &str = "default";
+const MACRO_BODY_NAME: &str = "body";
+const MACRO_KEEP_NAME: &str = "keep";
+const MACRO_RAW_NAME: &str = "raw";
 
-const STANDARD_MACROS: &'static [&'static str] = &[
+const STANDARD_MACROS: &[&str] = &[
     "dbg",
     "print",
     "println",
+    "eprint",
+    "eprintln",
+    "format",
+    "format_args",
+    "write",
+    "writeln",
+    "panic",
+    "unreachable",
+    "todo",
+    "unimplemented",
     "assert",
     "assert_eq",
     "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "anyhow",
+    "bail",
+    "ensure",
+    "format_err",
+    "eyre",
+];
+
+/// Attributes of async runtimes which are removed from functions in the sync version.
+const RUNTIME_ATTRS: &[&str] = &[
+    "tokio::main",
+    "tokio::test",
+    "async_std::main",
+    "async_std::test",
 ];
 
 /// Marks the code that can be presented in several versions. 
@@ -214,11 +258,45 @@ const STANDARD_MACROS: &'static [&'static str] = &[
 /// - `disable` 
 /// 
 ///     The macro with `disable` parameter will do nothing, like `noop`. Use it to write and debug 
-/// initial async code.
+/// initial async code. It cannot be combined with versions (`sync(...)`, `async(...)`): disable the
+/// whole macro or remove the `disable` parameter.
+///
+/// - `force`
+///
+///     Always run the full conversion of the item, even if it looks mode-agnostic (has no async
+/// code) or `disable` comes from a template (`use = "..."`): `force` takes precedence over
+/// `disable`, which may then be combined with versions.
 ///
 /// - `prefix` 
 /// 
-///     The name of `maybe-async-cfg` crate. If not set, `"maybe_async_cfg"` will be used.
+///     The name of `maybe-async-cfg` crate. If not set, `"maybe_async_cfg"` will be used. Versions
+/// inherit it, but a version may override it (for example, `sync(prefix = "mac")`): the attribute
+/// re-emitted for that version is then `#[mac::maybe(...)]`.
+///
+/// - `macro_path`
+///
+///     The full path of the `maybe` macro, for crates which re-export the macros at a deeper path
+/// (for example, `macro_path = "my_crate::macros::maybe"`). The attributes emitted for versions use
+/// this path, and the other macros (`only_if`, `remove_if` and so on) are expected in the same
+/// module, so re-export all of them (`pub use maybe_async_cfg::*;`). Overrides `prefix`.
+///
+/// - `keep_unsupported`
+///
+///     An item of a kind that can't be converted (e.g. `mod`) normally aborts the expansion. With
+/// `keep_unsupported`, the error is reported, but the item is kept as is and the conversion goes
+/// on, so the other items of the crate still convert and uses of the item don't produce more
+/// errors (useful for IDEs).
+///
+/// - `strict_params`
+///
+///     Unknown parameters are normally interpreted as inner attributes. With `strict_params`, an
+/// unknown parameter which looks like a misspelled known one (for example, `diasble`) produces an
+/// error instead. Standard attributes like `test` or `inline` are still accepted.
+///
+/// - `use`
+///
+///     `use = "name"` inserts parameters of the template defined earlier by the `define!` macro.
+/// If the crate is imported under another name, `prefix` must be given next to `use`.
 ///
 /// - `sync`, `async` 
 /// 
@@ -228,7 +306,14 @@ const STANDARD_MACROS: &'static [&'static str] = &[
 ///
 ///     For the `sync` version, the item will be converted from async to sync code by deleting
 /// the `async` and `await` keywords. The types `Future<Output=XXX>` will also be replaced with just
-/// `XXX`. For the  `async` version, the item will be left async.
+/// `XXX`: generic parameters of functions bounded by `Future<Output=XXX>` are removed and replaced
+/// with `XXX`, so `F: Fn() -> Fut, Fut: Future<Output=XXX>` becomes `F: Fn() -> XXX` (generics of
+/// structs, impls and traits are kept). `impl Future<Output=XXX> + '_` returned from functions
+/// is replaced with `XXX`, and `async move {...}` with its body (boxed futures and async blocks
+/// returned by closures, `|| async {...}`, are converted only with `rewrite_boxed`; `async` of
+/// async closures is always removed). The `'async_trait` lifetime left by `async_trait` expansions is removed
+/// from generics, bounds and references. For the  `async`
+/// version, the item will be left async.
 ///
 ///     In any case, the item will be converted according to all the parameters described below. For
 /// functions, structs/enums and traits, the name will be changed as if it is mentioned in the
@@ -300,7 +385,200 @@ const STANDARD_MACROS: &'static [&'static str] = &[
 ///         f: Foo,
 ///     }
 ///     ```
+///
+/// - `key_cfg`
+///
+///     Adds the condition `feature = "<key>"`, where `<key>` is the key of the current version. If
+/// the `cfg` condition is present too, both conditions are combined with `all(...)`.
+///
+///     ```rust, no_run
+///     #[maybe_async_cfg::maybe(
+///         key_cfg,
+///         sync(key="use_sync"),
+///         async(key="use_async")
+///     )]
+///     struct Struct {
+///         f: usize,
+///     }
+///
+///     ```
+///     After convertation:
+///     ```rust, no_run
+///     #[cfg(feature="use_sync")]
+///     struct StructSync {
+///         f: usize,
+///     }
+///     #[cfg(feature="use_async")]
+///     struct StructAsync {
+///         f: usize,
+///     }
+///     ```
+///
+/// - `auto_cfg`
+///
+///     Adds the condition `feature = "<name>"` to each version by its kind, so the same feature
+/// condition does not have to be repeated in every version of every item:
+/// `auto_cfg(sync = "sync", async = "async")` gates the sync versions with `feature = "sync"` and
+/// the async ones with `feature = "async"`. Other conditions of the version (`cfg`, `key_cfg`)
+/// are combined with it by `all(...)`.
+///
+/// - `vis`
+///
+///     Overrides the visibility of the item by the version kind: with `vis(sync = "pub(crate)",
+/// async = "pub")` the sync version is `pub(crate)` and the async one is `pub`, whatever the
+/// visibility of the original item. An empty string makes the version private.
+///
+/// - `conditional_attrs`
+///
+///     Emits one item instead of a copy per version, for items whose versions differ only by
+/// their attributes. The item keeps its name (as with `keep_self`) and is gated by `#[cfg(any(...))]`
+/// of the version conditions; attributes present in all versions are kept as is, the others are
+/// wrapped in `#[cfg_attr(<condition>, ...)]`. If the converted versions differ by anything else
+/// (`async`, `.await`, renamed identifiers, attributes of nested items), an error is reported.
+///
+///     ```rust, no_run
+///     #[maybe_async_cfg::maybe(
+///         conditional_attrs,
+///         sync(feature="use_sync", derive(Clone)),
+///         async(feature="use_async", derive(Clone, Debug)),
+///     )]
+///     struct Data(u32);
+///     ```
+///     After convertation:
+///     ```rust, no_run
+///     #[cfg(any(feature="use_sync", feature="use_async"))]
+///     #[cfg_attr(feature="use_sync", derive(Clone))]
+///     #[cfg_attr(feature="use_async", derive(Clone, Debug))]
+///     struct Data(u32);
+///     ```
 ///  
+/// - `trim_cfg`
+///
+///     Simplifies `#[cfg(...)]` attributes inside the item (for example, after `replace_features`)
+/// without changing their meaning: identical conditions are de-duplicated, nested `all`/`any` of the
+/// same kind are flattened and `all(x)`/`any(x)` become just `x`. So `cfg(all(feature="x",
+/// feature="x"))` becomes `cfg(feature="x")`. Empty `all()` (always true) and `any()` (always false)
+/// are kept as is.
+///
+/// - `keep_asyncness`
+///
+///     In the `sync` version, keeps functions and methods `async` while all other transformations
+/// (renaming, removing `.await` and so on) are still applied. This is meant for the narrow case of
+/// custom executors, where the functions must stay `async` but call the sync versions of the code.
+///
+/// - `keep_runtime_attrs`
+///
+///     In the `sync` version, async functions lose the entry attributes of async runtimes
+/// (`#[tokio::main]`, `#[tokio::test]`, `#[async_std::main]` and `#[async_std::test]`, with any
+/// arguments), so an async `main` becomes a plain `fn main()`. Add `test` to the sync version
+/// (`sync(test)`) to keep a test running. With `keep_runtime_attrs` (or `keep_asyncness`) the
+/// attributes are kept.
+///
+/// - `reexport`
+///
+///     For a renamed function, struct, enum, trait, const, static or type alias, also emits
+/// `use self::FooSync as Foo;` with the visibility of the item, so the canonical name refers to
+/// this version. Enable it in one version only (or in versions that are never enabled together),
+/// otherwise the names clash.
+///
+///     ```rust, no_run
+///     #[maybe_async_cfg::maybe(sync(feature="use_sync", reexport), async(feature="use_async"))]
+///     pub async fn read() -> u32 {
+///         1
+///     }
+///     ```
+///     After convertation:
+///     ```rust, no_run
+///     #[cfg(feature="use_sync")]
+///     pub fn read_sync() -> u32 {
+///         1
+///     }
+///     #[cfg(feature="use_sync")]
+///     pub use self::read_sync as read;
+///     #[cfg(feature="use_async")]
+///     pub async fn read_async() -> u32 {
+///         1
+///     }
+///     ```
+///
+/// - `rewrite_macro_body`
+///
+///     By default only the name of a `macro_rules!` definition is converted (as a function name,
+/// `double!` becomes `double_sync!`), its rules are kept as is. With this parameter the
+/// transcribers of the rules are converted too, token by token: identifiers listed in `idents` are
+/// renamed and `.await` is removed in the sync version. The matchers are never changed.
+///
+/// - `strip_macro_awaits`
+///
+///     Removes `.await` in the sync version from the bodies of macro calls whose syntax is unknown
+/// (`vec![fetch().await]`, `my_macro!(...)`), token by token, without renaming (`.await` in
+/// string literals is kept). The tokens are not parsed, so every `.await` goes, including the ones
+/// in the input of macros like `stringify!`, hence it's opt-in. Prefer `transparent_macros` for
+/// macros taking expressions.
+///
+/// - `rewrite_spawn`
+///
+///     In the sync version, calls of `spawn` functions with an async block (`tokio::spawn(async
+/// move {...})`, `tokio::task::spawn(...)`, `async_std::task::spawn(...)` and so on) become
+/// `std::thread::spawn(move || {...})`. Note that the returned handles differ: awaiting a tokio
+/// `JoinHandle` gives a `Result`, while the sync version gets a `std::thread::JoinHandle`, so use
+/// channels or `body!` to collect the result.
+///
+///     A thread requires a `Send` closure, so the rewrite is not applied in a version with
+/// `send = "?Send"` (e.g. `sync(send = "?Send")`): the spawn call is kept and only its async
+/// block is converted. `rewrite_stream` is not applied in such a version either, while
+/// `rewrite_sleep` doesn't depend on `Send` and is not affected.
+///
+/// - `rewrite_sleep`
+///
+///     In the sync version, `tokio::time::sleep(d).await` and `async_std::task::sleep(d).await`
+/// become `std::thread::sleep(d)`, and `tokio::time::sleep_until(t).await` becomes
+/// `std::thread::sleep(t.into_std().saturating_duration_since(std::time::Instant::now()))`, which
+/// uses the std clock. The deadline is still a `tokio::time::Instant`, so the sync version of the
+/// code calling `sleep_until` depends on `tokio` (with the `time` feature) as well. The functions
+/// must be called by their full paths.
+///
+/// - `rewrite_boxed`
+///
+///     In the sync version, boxed futures returned from functions (`Pin<Box<dyn
+/// Future<Output=T>>>`, `BoxFuture<'_, T>` and `LocalBoxFuture<'_, T>`) are replaced with `T`,
+/// `fut.boxed()` and `fut.boxed_local()` (from `futures::FutureExt`) become `fut`, and
+/// `Box::pin(fut)` becomes `fut` (`Box::pin(async move {...})` becomes the body of the block). The
+/// async block returned by a closure is flattened as well: `|| async move {...}` becomes
+/// `|| {...}`.
+///
+///     Without it boxed futures are kept in the sync version: `Box::pin(async move {...})` stays
+/// a boxed async block and `|| async move {...}` still returns a future, only the awaits inside
+/// them are removed. `Box::pin(fut).await` is always converted to `fut`.
+///
+/// - `sync_via_block_on`
+///
+///     Makes the sync version of an async function a blocking shim: instead of being converted to
+/// sync code, the body is converted as in the async version and wrapped in
+/// `futures::executor::block_on(async { ... })`. The signature is converted as usual (`async` is
+/// removed, the return type is kept). Another executor can be given by path:
+/// `sync_via_block_on = "tokio_test::block_on"`. Note that `.await` is *not* removed inside the
+/// wrapped body, and calls of functions listed in `idents` refer to their async versions.
+///
+/// - `dump`
+///
+///     Reports the code generated for each version of the item as a warning while compiling, which
+/// helps to debug `idents` and other rules. The warning is the deprecation note of a hidden item,
+/// so it's shown by stable compilers too. If the `MAYBE_ASYNC_CFG_DUMP_DIR` environment variable
+/// is set, the code is also written to `<dir>/<item>.<mode>.<hash>.rs` (e.g.
+/// `fetch_sync.sync.0123456789abcdef.rs`), where `<hash>` is the hash of the code, which keeps
+/// apart items of the same name. The code is preceded by a comment with the counts of the
+/// conversion: `// renames: 2, awaits stripped: 1, attributes dropped: 0, features replaced: 0`
+/// (see also "Conversion report").
+///
+/// - `check_features`
+///
+///     Check that the features used in the conditions of versions (`feature = "..."` in `sync`,
+/// `async`, `cfg` and `key` with `key_cfg`) are declared in `Cargo.toml` of the crate (the
+/// `[features]` table or optional dependencies), and report an error for each undeclared one.
+/// Without the check a misspelled feature silently disables the version. It is opt-in, because
+/// the manifest is read on every expansion of the macro.
+///
 /// - `idents` 
 /// 
 ///     Defines a list of identifiers that should be renamed depending on the version of the code.
@@ -310,13 +588,21 @@ const STANDARD_MACROS: &'static [&'static str] = &[
 ///     - `fn`
 ///
 ///         means that this is the name of the function and it should be converted by adding the
-/// suffixes `"_sync"`/`"_async"` (otherwise, the suffixes `"Sync"`/`"Async"` will be used).
+/// suffixes `"_sync"`/`"_async"` (otherwise, the suffixes `"Sync"`/`"Async"` will be used). The
+/// names in upper case (like the names of `const` and `static` items) get the suffixes
+/// `"_SYNC"`/`"_ASYNC"`.
 ///
 ///     - `use`  
 /// 
 ///         in `use` lists, using this identifier will result in renaming via the `as` expression, 
 /// rather than a simple replacement as is. In other cases, a simple replacement will be used.
 ///
+///     - `method`
+///
+///         method calls with this name on any receiver (`child.depth()`) are renamed too. Without
+/// it only the calls on `self` are renamed, since other receivers may be foreign types
+/// (`file.read()`).
+///
 ///     - `keep`
 ///
 ///         this identifier will not be converted anywhere
@@ -327,6 +613,27 @@ const STANDARD_MACROS: &'static [&'static str] = &[
 /// the standard scheme of suffixes used by default. If the parameter value is omitted, 
 /// the identifier will not be renamed in this case.
 ///
+///     An identifier may be qualified with a module path (`a::Client`): then only the paths ending
+/// with `a::Client` are renamed (its last segment), while `b::Client` or bare `Client` are kept.
+///
+///     A type alias can be listed in `idents` like any other type. When the aliased types are
+/// unrelated, declare one alias per version by hand (`type ClientSync = BlockingClient;`,
+/// `type ClientAsync = AsyncClient;`), then `Client::connect().await` calls the right one.
+///
+///     Identifiers are also renamed inside the arguments of standard formatting and assertion
+/// macros (`format!`, `println!`, `write!`, `panic!`, `assert_eq!` and so on) and error
+/// constructors (`anyhow!`, `bail!`, `ensure!`, `format_err!`, `eyre!`), as well as in the
+/// scrutinee and the guard of `matches!`. The format string and the pattern are left intact,
+/// `.await` in the arguments is removed in the sync version. The bodies of `stream!` and
+/// `try_stream!` (from `async-stream` crate) are converted as blocks (macros of the same name
+/// whose bodies are not statements are left unchanged): `yield` is kept as is, so
+/// the sync version of the body still needs a sync replacement of these macros. Bodies of other
+/// macros (`vec![fetch().await]`) are left as is, unless the macros are listed in
+/// `transparent_macros` or `strip_macro_awaits` is set (see below).
+///
+///     Several `idents` groups are merged. The identifiers of a version (`sync(idents(...))`) are
+/// added to the common ones, and override them if the same identifier is given in both.
+///
 ///     ```rust, no_run
 ///     #[maybe_async_cfg::maybe(
 ///         idents(
@@ -397,15 +704,61 @@ const STANDARD_MACROS: &'static [&'static str] = &[
 /// 
 ///     Defines the name that will be assigned to the item in this variant.
 /// 
+/// - `as_name`
+///
+///     Defines the exact name of the converted item in this version, regardless of the suffix
+/// rules, `idents` and `keep_self`. Unlike `self`, it does not need a `key`.
+///
+///     ```rust, no_run
+///     #[maybe_async_cfg::maybe(sync(as_name = "read_blocking"), async())]
+///     async fn read() -> u32 {
+///         1
+///     }
+///     ```
+///     After convertation:
+///     ```rust, no_run
+///     fn read_blocking() -> u32 {
+///         1
+///     }
+///     async fn read_async() -> u32 {
+///         1
+///     }
+///     ```
+/// 
 /// - `send`
 ///
 ///     If `send = "Send"` or `send = "true"` is present, the attribute
 /// `#[async_trait::async_trait]` will be added before the async code. If `send = "?Send"` or
 /// `send = "false"` then `#[async_trait::async_trait(?Send)]` will be added.  
 /// 
+/// - `async_trait_cfg`
+///
+///     Adds the `async_trait` attribute to impl blocks only under the specified condition:
+/// `async_trait_cfg(feature = "boxed")` produces `#[cfg_attr(feature = "boxed",
+/// async_trait::async_trait)]`. The `send` parameter still selects `Send` or `?Send`; without it
+/// `Send` is used. This helps to migrate from `async_trait` to native async traits gradually.
+/// 
+/// - `async_trait`
+///
+///     Selects how async methods of traits are compiled in the async version. The default
+/// `async_trait = "async_trait"` adds the `async_trait` attribute to impl blocks as described for
+/// `send`. With `async_trait = "native"` no attribute is added and `async fn` in traits is left as
+/// is (Rust 1.75+), so the `async-trait` dependency is not needed. If `send = "Send"` is also set,
+/// async methods of the trait are declared as `fn ... -> impl Future<Output = ...> + Send` (default
+/// bodies are wrapped in `async move` and get the `Self: Sync` or `Self: Send` bound for the
+/// receiver), so that all implementations must return `Send` futures.
+///
+///     `async_trait = "trait_variant"` leaves `async fn` as is too, and with `send` uses the
+/// `trait-variant` crate: the async version of `trait Foo` becomes `#[trait_variant::make(Send)]
+/// trait FooAsync` with `send = "Send"`, so its async methods must return `Send` futures, and
+/// `#[trait_variant::make()] trait FooAsync` with `send = "?Send"`. Without `send` nothing is
+/// added.
+///
 /// - `drop_attrs`
 ///
-///     Remove any attributes with specified names.
+///     Remove any attributes with specified names. Entries may be paths (`drop_attrs(tokio::test)`)
+/// or path prefixes given as strings: `drop_attrs("tokio::*")` removes `#[tokio::test]`,
+/// `#[tokio::main]` and any other attribute under `tokio::`.
 ///
 ///     ```rust, no_run
 ///     #[maybe_async_cfg::maybe(
@@ -435,6 +788,100 @@ const STANDARD_MACROS: &'static [&'static str] = &[
 ///     }
 ///     ```
 ///
+/// - `drop_args`
+///
+///     Remove function arguments with specified names from all signatures. The call sites are not
+/// changed.
+///
+///     ```rust, no_run
+///     #[maybe_async_cfg::maybe(
+///         sync(feature="use_sync", drop_args(cx)),
+///         async(feature="use_async"),
+///     )]
+///     async fn poll_read(buf: &mut [u8], cx: &mut std::task::Context<'_>) -> usize {
+///         todo!()
+///     }
+///     ```
+///     After convertation:
+///     ```rust, no_run
+///     #[cfg(feature="use_sync")]
+///     fn poll_read_sync(buf: &mut [u8]) -> usize {
+///         todo!()
+///     }
+///     #[cfg(feature="use_async")]
+///     async fn poll_read_async(buf: &mut [u8], cx: &mut std::task::Context<'_>) -> usize {
+///         todo!()
+///     }
+///     ```
+///
+/// - `transparent_macros`
+///
+///     Adds macros whose arguments are converted as the arguments of the standard formatting
+/// macros: with `transparent_macros(my_try)`, `my_try!(fetch().await, "context")` becomes
+/// `my_try!(fetch_sync(), "context")` in the sync version (if `fetch` is in `idents`). The
+/// arguments must be comma separated expressions. Macros are matched by the last path segment.
+///
+/// - `drop_bounds`
+///
+///     Remove trait bounds with specified names from generic parameters and where clauses of all
+/// items (including methods). Bounds are matched by the last path segment, predicates left without
+/// bounds are removed.
+///
+///     ```rust, no_run
+///     #[maybe_async_cfg::maybe(
+///         sync(feature="use_sync", drop_bounds(Send)),
+///         async(feature="use_async"),
+///     )]
+///     async fn run<T: Clone + Send>(value: T) -> T {
+///         value.clone()
+///     }
+///     ```
+///     After convertation:
+///     ```rust, no_run
+///     #[cfg(feature="use_sync")]
+///     fn run_sync<T: Clone>(value: T) -> T {
+///         value.clone()
+///     }
+///     #[cfg(feature="use_async")]
+///     async fn run_async<T: Clone + Send>(value: T) -> T {
+///         value.clone()
+///     }
+///     ```
+///
+/// - `drop_pin`
+///
+///     Remove `#[pin_project]` attributes from structs and enums and `#[pin]` attributes from their
+/// fields. Enabled by default for sync versions (plain structs don't need pin projection), can be
+/// set with `drop_pin = "false"` or `drop_pin = "true"`.
+///
+///     ```rust, no_run
+///     #[maybe_async_cfg::maybe(
+///         sync(feature="use_sync"),
+///         async(feature="use_async"),
+///     )]
+///     #[pin_project::pin_project]
+///     struct Timed<F> {
+///         #[pin]
+///         inner: F,
+///         elapsed: u32,
+///     }
+///     ```
+///     After convertation:
+///     ```rust, no_run
+///     #[cfg(feature="use_sync")]
+///     struct TimedSync<F> {
+///         inner: F,
+///         elapsed: u32,
+///     }
+///     #[cfg(feature="use_async")]
+///     #[pin_project::pin_project]
+///     struct TimedAsync<F> {
+///         #[pin]
+///         inner: F,
+///         elapsed: u32,
+///     }
+///     ```
+///
 /// - `replace_features`
 ///
 ///     Replace one feature name with another.
@@ -467,6 +914,141 @@ const STANDARD_MACROS: &'static [&'static str] = &[
 ///     }
 ///     ```
 ///
+///     The replacement may also be a whole cfg predicate, e.g. `replace_feature("secure",
+/// all(feature = "tls", not(feature = "insecure")))`, which takes the place of `feature =
+/// "secure"`. An empty string removes the condition: it is treated as always true, and a `cfg`
+/// attribute consisting only of it is dropped.
+///
+///     Replacements given at the top level also apply to the conditions of the versions:
+/// `replace_feature("a", "b")` turns `sync(feature = "a")` into `sync(feature = "b")`.
+///
+/// - `rename_crate`
+///
+///     Replace the leading crate segment of qualified type and expression paths. The rest of the
+/// path, including generic arguments, is kept as is.
+///
+///     ```rust, no_run
+///     #[maybe_async_cfg::maybe(
+///         sync(feature="use_sync", rename_crate(tokio="std")),
+///         async(feature="use_async"),
+///     )]
+///     struct Struct {
+///         lock: tokio::sync::Mutex<usize>,
+///     }
+///     ```
+///     After convertation:
+///     ```rust, no_run
+///     #[cfg(feature="use_sync")]
+///     struct StructSync {
+///         lock: std::sync::Mutex<usize>,
+///     }
+///     #[cfg(feature="use_async")]
+///     struct StructAsync {
+///         lock: tokio::sync::Mutex<usize>,
+///     }
+///     ```
+///
+/// - `replace_ident`
+///
+///     Replace an identifier starting a type or expression path with a path given for each version
+/// (`sync = "..."`, `async = "..."`); a version without a value keeps the identifier. Unlike
+/// `idents`, the replacement may be any path, and the referenced item does not have to be
+/// converted by `maybe`. Generic arguments and the rest of the path are kept. The name of the
+/// converted item itself is not replaced (it is renamed as usual), so the parameter can be shared
+/// by the item and the code using it.
+///
+///     ```rust, no_run
+///     #[maybe_async_cfg::maybe(
+///         replace_ident("BufReader", sync = "std::io::BufReader", async = "tokio::io::BufReader"),
+///         sync(feature="use_sync"),
+///         async(feature="use_async"),
+///     )]
+///     struct Struct<R> {
+///         reader: BufReader<R>,
+///     }
+///     ```
+///     After convertation:
+///     ```rust, no_run
+///     #[cfg(feature="use_sync")]
+///     struct StructSync<R> {
+///         reader: std::io::BufReader<R>,
+///     }
+///     #[cfg(feature="use_async")]
+///     struct StructAsync<R> {
+///         reader: tokio::io::BufReader<R>,
+///     }
+///     ```
+///
+/// - `replace_path`
+///
+///     Replace a path (or the leading segments of a longer path) with another path given for each
+/// version, like `replace_ident`. The segments are compared as a whole, the leading `::`, `crate::`
+/// and `self::` are ignored. Generic arguments of the last replaced segment and the rest of the path
+/// are kept, so `std::sync::Mutex::<T>::new` becomes `tokio::sync::Mutex::<T>::new`.
+///
+///     ```rust, no_run
+///     #[maybe_async_cfg::maybe(
+///         replace_path("std::sync::Mutex", async = "tokio::sync::Mutex"),
+///         sync(feature="use_sync"),
+///         async(feature="use_async"),
+///     )]
+///     struct Struct {
+///         lock: std::sync::Mutex<usize>,
+///     }
+///     ```
+///     After convertation:
+///     ```rust, no_run
+///     #[cfg(feature="use_sync")]
+///     struct StructSync {
+///         lock: std::sync::Mutex<usize>,
+///     }
+///     #[cfg(feature="use_async")]
+///     struct StructAsync {
+///         lock: tokio::sync::Mutex<usize>,
+///     }
+///     ```
+///
+/// - `methods`
+///
+///     Replace method calls with arbitrary expressions, useful when the equivalent of an async
+/// method is an associated function. Each entry is `method = "template"`. Only two placeholders
+/// are supported: `$self` is replaced with the receiver and `$args` with the arguments of the
+/// call. The result must be a valid expression.
+///
+///     ```rust, no_run
+///     #[maybe_async_cfg::maybe(
+///         sync(feature="use_sync", methods(connect_async = "Client::connect($self, $args)")),
+///         async(feature="use_async"),
+///     )]
+///     async fn run(client: &Client) {
+///         client.connect_async(5).await;
+///     }
+///     ```
+///     After convertation:
+///     ```rust, no_run
+///     #[cfg(feature="use_sync")]
+///     fn run_sync(client: &Client) {
+///         Client::connect(client, 5);
+///     }
+///     #[cfg(feature="use_async")]
+///     async fn run_async(client: &Client) {
+///         client.connect_async(5).await;
+///     }
+///     ```
+///
+/// - `rewrite_stream`
+///
+///     Rewrites `impl Iterator<Item = T>` return types into `impl Stream<Item = T>` in the async
+/// version. The value is the path to the `Stream` trait (`rewrite_stream = "futures::Stream"`), if
+/// omitted, `Stream` will be used. The bodies of the functions are not changed.
+///
+///     For async-first code the parameter works in the other direction too: in the sync version
+/// `impl Stream<Item = T>` return types become `impl Iterator<Item = T>`, `impl TryStream<Ok = T,
+/// Error = E>` becomes `impl Iterator<Item = Result<T, E>>`, and `s.try_next().await` becomes
+/// `s.next().transpose()` (`s.next().await` becomes `s.next()` as any other `.await`).
+///
+///     None of these rewrites is applied in a version with `send = "?Send"` (see `rewrite_spawn`).
+///
 /// - `inner`, `outer`
 ///
 ///     Adds some attributes to the generated code. Inner attributes will appear below attribute 
@@ -477,6 +1059,9 @@ const STANDARD_MACROS: &'static [&'static str] = &[
 /// 
 ///     Useful for testing: just write `test` in version parameters.
 ///
+///     Name-value attributes work as well, e.g. per-version documentation:
+/// `sync(inner(doc = "Blocking version."))`.
+///
 ///     ```rust, no_run
 ///     #[maybe_async_cfg::maybe(
 ///         sync(feature="secure_sync", test, "resource(path = \"/foo/bar\")", outer(xizzy)),
@@ -502,6 +1087,13 @@ const STANDARD_MACROS: &'static [&'static str] = &[
 ///         todo!()
 ///     }
 ///     ```
+///
+/// - `add_attrs`
+///
+///     Appends attributes to converted items depending on the mode:
+/// `add_attrs(sync(test), async(tokio::test))` adds `#[test]` to the sync version and
+/// `#[tokio::test]` to the async one. Attributes may be given as metas or as string literals (like
+/// `inner`). Unlike `inner`, it can be used at the top level, where it applies to all versions.
 /// 
 /// - In other cases, the following rules apply:
 ///     
@@ -513,6 +1105,20 @@ const STANDARD_MACROS: &'static [&'static str] = &[
 /// 
 ///     - all another parameters will be interpreted as inner attribute for current version (as 
 /// wrapped in `inner(...)`).
+///
+/// A method of a converted `impl` or `trait` may have its own `maybe` attribute without versions:
+/// its parameters (`idents`, `drop_attrs`, `replace_ident`, ...) are applied over the ones of the
+/// item, for this method only, and the attribute is removed.
+///
+/// ```rust, no_run
+/// #[maybe_async_cfg::maybe(idents(Store), sync(feature="use_sync"), async(feature="use_async"))]
+/// impl Store {
+///     #[maybe_async_cfg::maybe(idents(fetch(fn)))]
+///     async fn get(&self) -> u32 {
+///         fetch().await
+///     }
+/// }
+/// ```
 /// 
 #[proc_macro_error]
 #[proc_macro_attribute]
@@ -549,6 +1155,124 @@ pub fn remove(_: TokenStream, _: TokenStream) -> TokenStream {
     TokenStream::new()
 }
 
+/// Selects the body of a function for the current version.
+///
+/// Use it as the only statement of a function body when the sync and async bodies are completely
+/// different. The `maybe` macro replaces it with the block of the version being generated; used
+/// outside of `maybe`, it produces an error. The path must be written with the prefix
+/// (`maybe_async_cfg::body!`).
+///
+/// ```rust, no_run
+/// #[maybe_async_cfg::maybe(sync(feature="use_sync"), async(feature="use_async"))]
+/// async fn read() -> Vec<u8> {
+///     maybe_async_cfg::body!(
+///         sync = { std::fs::read("file").unwrap() },
+///         async = { async_std::fs::read("file").await.unwrap() },
+///     )
+/// }
+/// ```
+/// After convertation:
+/// ```rust, no_run
+/// #[cfg(feature="use_sync")]
+/// fn read_sync() -> Vec<u8> {
+///     { std::fs::read("file").unwrap() }
+/// }
+/// #[cfg(feature="use_async")]
+/// async fn read_async() -> Vec<u8> {
+///     { async_std::fs::read("file").await.unwrap() }
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro]
+pub fn body(_: TokenStream) -> TokenStream {
+    proc_macro_error::abort_call_site!("`body!` can be used only inside an item converted by `maybe`")
+}
+
+/// Keeps the wrapped expression as is in all versions: `.await` inside it is not removed in the
+/// sync version.
+///
+/// Use it for call sites where the sync version still needs `.await`-like code, e.g. a future that
+/// is polled manually. The wrapper itself is removed. The path must be written with the prefix
+/// (`maybe_async_cfg::keep!`); used outside of `maybe`, it produces an error.
+///
+/// The expression is kept verbatim as a whole: if it contains several `.await`s, none of them is
+/// removed, and `idents` are not renamed inside it. Wrap only the part that must be kept.
+///
+/// ```rust, no_run
+/// #[maybe_async_cfg::maybe(sync(feature="use_sync"), async(feature="use_async"))]
+/// async fn run(fut: MyFuture) -> u32 {
+///     maybe_async_cfg::keep!(fut.await)
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro]
+pub fn keep(_: TokenStream) -> TokenStream {
+    proc_macro_error::abort_call_site!("`keep!` can be used only inside an item converted by `maybe`")
+}
+
+/// Injects raw items before the marked item in the specified version.
+///
+/// Use it as an escape hatch for mode-specific code that the conversion can't express. It must be
+/// placed on the item marked by `maybe`, below the `maybe` attribute. Each string must parse as a
+/// sequence of items; used outside of `maybe`, the attribute produces an error.
+///
+/// ```rust, no_run
+/// #[maybe_async_cfg::maybe(sync(feature="use_sync"), async(feature="use_async"))]
+/// #[maybe_async_cfg::raw(sync = "use std::fs::read;", async = "use async_std::fs::read;")]
+/// async fn load() -> Vec<u8> {
+///     read("file").await.unwrap()
+/// }
+/// ```
+/// After convertation:
+/// ```rust, no_run
+/// #[cfg(feature="use_sync")]
+/// use std::fs::read;
+/// #[cfg(feature="use_sync")]
+/// fn load_sync() -> Vec<u8> {
+///     read("file").unwrap()
+/// }
+/// #[cfg(feature="use_async")]
+/// use async_std::fs::read;
+/// #[cfg(feature="use_async")]
+/// async fn load_async() -> Vec<u8> {
+///     read("file").await.unwrap()
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn raw(_: TokenStream, _: TokenStream) -> TokenStream {
+    proc_macro_error::abort_call_site!("`raw` can be used only on an item converted by `maybe`")
+}
+
+/// Defines a named template of `maybe` parameters.
+///
+/// The template can be referenced as `#[maybe(use = "name")]`, its parameters are inserted in
+/// place of `use`, other parameters of the `maybe` are kept.
+///
+/// ```rust, no_run
+/// maybe_async_cfg::define!(versions, sync(feature="use_sync"), async(feature="use_async"));
+///
+/// #[maybe_async_cfg::maybe(use = "versions", idents(Foo))]
+/// struct Struct {
+///     f: Foo,
+/// }
+///
+/// #[maybe_async_cfg::maybe(use = "versions")]
+/// async fn func() {
+///     todo!()
+/// }
+/// ```
+///
+/// The template is a `macro_rules!` macro with the name of the template, `maybe` passes the item
+/// to it and the macro puts the item back under `maybe` with the parameters of the template. So
+/// the template follows the scoping of `macro_rules!`: it is available only below its definition
+/// and its name must not clash with other macros in scope.
+#[proc_macro_error]
+#[proc_macro]
+pub fn define(body: TokenStream) -> TokenStream {
+    macros::define(body)
+}
+
 /// A wrapper for code with common `maybe` parameters
 /// 
 /// The `content` macro allows you to specify common parameters for many `maybe` macros. Use the
@@ -1,23 +1,24 @@
-use proc_macro_error::abort;
+use proc_macro_error::{abort, emit_error};
 
 #[allow(unused_imports)]
 use std::iter::FromIterator;
 
 use proc_macro::TokenStream;
 
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use syn::{
-    parse_macro_input, spanned::Spanned, visit_mut::VisitMut, File, ImplItem, ItemEnum, ItemFn,
-    ItemImpl, ItemStruct, ItemTrait, ItemUse, TraitItem, Type, TypePath,
+    parse::Parser, parse_macro_input, spanned::Spanned,
+    AttributeArgs, Lit, LitStr, Meta, MetaNameValue, NestedMeta, visit_mut::VisitMut, Attribute, Fields, File, FnArg, Ident, ImplItem, Item, ItemConst, ItemEnum, ItemFn,
+    ItemImpl, ItemMacro, ItemStatic, ItemStruct, ItemTrait, ItemType, ItemUse, ReturnType, TraitItem, TraitItemMethod, Type, TypePath, Visibility,
 };
 
 #[allow(unused_imports)]
 use quote::{quote, ToTokens};
 
 use crate::{
-    MACRO_MAYBE_NAME,
-    params::{ConvertMode, MacroParameters},
-    utils::{make_attr_from_str, unwrap_or_error},
+    MACRO_MAYBE_NAME, MACRO_RAW_NAME, RUNTIME_ATTRS,
+    params::{AsyncTraitBackend, ConvertMode, MacroParameters},
+    utils::{attr_path_to_string, cfg_features, edit_distance, make_attr_from_str, make_path, manifest_features, unwrap_or_error, TemplateDefinition},
     visit_ext::Visitor,
     visitor_async::AsyncAwaitVisitor,
     visitor_content::ContentVisitor,
@@ -29,6 +30,11 @@ use crate::{
 pub fn maybe(args: TokenStream, input: TokenStream) -> TokenStream {
     dump_maybe!(&args, &input);
 
+    if let Some(tokens) = unwrap_or_error!(expand_template(args.clone(), input.clone())) {
+        dump_tokens!("maybe after", &tokens);
+        return tokens;
+    }
+
     let params = unwrap_or_error!(MacroParameters::from_tokens(args));
     dump_params!("maybe params", &params);
 
@@ -40,6 +46,16 @@ pub fn maybe(args: TokenStream, input: TokenStream) -> TokenStream {
         return convert(params, input, convert_mode)
     }
 
+    if params.check_features_get() {
+        check_features(&params);
+    }
+
+    if params.conditional_attrs_get() {
+        let tokens = unwrap_or_error!(merge_versions(&params, input));
+        dump_tokens!("maybe after", &tokens);
+        return tokens;
+    }
+
 
     let mut tokens = TokenStream::new();
 
@@ -50,8 +66,8 @@ pub fn maybe(args: TokenStream, input: TokenStream) -> TokenStream {
             ConvertMode::IntoAsync | ConvertMode::IntoSync => {
                 let _ = unwrap_or_error!(version
                     .params
-                    .extend_tokenstream2_with_cfg_outer_attrs(&mut ts));
-                let name = params.make_self_path(MACRO_MAYBE_NAME);
+                    .extend_tokenstream2_with_cfg_outer_attrs(&mut ts, version.kind));
+                let name = version.params.make_self_path(MACRO_MAYBE_NAME);
                 let args = version.params.to_tokens(Some(version.kind));
                 ts.extend(quote!(#[#name(#args)]));
 
@@ -70,32 +86,352 @@ pub fn maybe(args: TokenStream, input: TokenStream) -> TokenStream {
     tokens
 }
 
+/// Reports features of the versions' conditions which are not declared in `Cargo.toml`: such a
+/// version would silently never be enabled.
+fn check_features(params: &MacroParameters) {
+    let declared = match manifest_features() {
+        Some(declared) => declared,
+        None => return,
+    };
+
+    for version in &params.versions {
+        let mut features = vec![];
+        if let Some(cfg) = version.params.cfg_get() {
+            cfg_features(cfg, &mut features);
+        }
+        if let (Some(key), true) = (version.params.key_get(), version.params.key_cfg_get()) {
+            features.push(LitStr::new(key, Span::call_site()));
+        }
+        if let Some(feature) = version.params.auto_cfg_get(version.kind) {
+            features.push(LitStr::new(feature, Span::call_site()));
+        }
+
+        for feature in features {
+            let name = feature.value();
+            if declared.contains(&name) {
+                continue;
+            }
+
+            let suggestion = declared
+                .iter()
+                .map(|known| (edit_distance(&name, known), known))
+                .filter(|(distance, _)| *distance <= 2)
+                .min_by_key(|(distance, _)| *distance);
+            match suggestion {
+                Some((_, known)) => emit_error!(
+                    feature.span(),
+                    "Feature `{}` is not declared in Cargo.toml, did you mean `{}`?", name, known
+                ),
+                None => emit_error!(feature.span(), "Feature `{}` is not declared in Cargo.toml", name),
+            }
+        }
+    }
+}
+
+/// `conditional_attrs`: the versions are converted right away and merged into one item. Attributes
+/// present in every version are kept as is, the others are wrapped in `#[cfg_attr(<condition of
+/// the versions>, ...)]`, and the item is gated by `#[cfg(any(<conditions>))]`.
+fn merge_versions(params: &MacroParameters, input: TokenStream) -> syn::Result<TokenStream> {
+    let mut versions = vec![];
+    for version in &params.versions {
+        // inner attributes are a part of the item seen by the version, as if it was expanded
+        let mut ts = TokenStream2::new();
+        version.params.extend_tokenstream2_with_inner_attrs(&mut ts)?;
+        ts.extend(TokenStream2::from(input.clone()));
+
+        let version_params = MacroParameters::from_tokens(version.params.to_tokens(Some(version.kind)).into())?;
+        let mut file = syn::parse::<File>(convert(version_params, ts.into(), version.kind))?;
+
+        let mut outer = TokenStream2::new();
+        version.params.extend_tokenstream2_with_outer_attrs(&mut outer)?;
+        if let Some(attrs) = file.items.first_mut().and_then(item_attrs_mut) {
+            let outer = Attribute::parse_outer.parse2(outer)?;
+            attrs.splice(0..0, outer);
+        }
+
+        versions.push((version.params.cfg_cond(version.kind), version.kind, file.items));
+    }
+
+    let (first_kind, first_items) = match versions.first() {
+        Some((_, kind, items)) => (*kind, items.clone()),
+        None => return Ok(TokenStream::new()),
+    };
+
+    let differ = |kind: ConvertMode| {
+        syn::Error::new(
+            Span::call_site(),
+            format!(
+                "`conditional_attrs` requires the versions to differ only by attributes, but the `{}` and `{}` versions differ (by `async`, `.await`, renamed identifiers and so on)",
+                first_kind.to_str(),
+                kind.to_str(),
+            ),
+        )
+    };
+
+    if let Some((_, kind, _)) = versions.iter().find(|(_, _, items)| items.len() != first_items.len()) {
+        return Err(differ(*kind));
+    }
+
+    let mut merged = Vec::with_capacity(first_items.len());
+    for (index, mut item) in first_items.into_iter().enumerate() {
+        let bare = without_attrs(&item);
+
+        // attributes in the order of appearance with the indexes of the versions having them
+        let mut entries: Vec<(Attribute, Vec<usize>)> = vec![];
+        for (version, (_, kind, items)) in versions.iter_mut().enumerate() {
+            let other = &mut items[index];
+            if without_attrs(other) != bare {
+                return Err(differ(*kind));
+            }
+
+            for attr in item_attrs_mut(other).map(std::mem::take).unwrap_or_default() {
+                let key = attr.to_token_stream().to_string();
+                let entry = entries
+                    .iter_mut()
+                    .find(|(known, owners)| known.to_token_stream().to_string() == key && !owners.contains(&version));
+                match entry {
+                    Some((_, owners)) => owners.push(version),
+                    None => entries.push((attr, vec![version])),
+                }
+            }
+        }
+
+        let mut attrs: Vec<Attribute> = vec![];
+        // a version without conditions is always enabled
+        if let Some(conds) = versions.iter().map(|(cond, _, _)| cond.as_ref()).collect::<Option<Vec<_>>>() {
+            attrs.push(syn::parse_quote!(#[cfg(any(#(#conds),*))]));
+        }
+        for (attr, owners) in entries {
+            if owners.len() == versions.len() {
+                attrs.push(attr);
+                continue;
+            }
+
+            let conds = owners
+                .iter()
+                .map(|&version| versions[version].0.clone().unwrap_or_else(|| quote!(all())))
+                .collect::<Vec<_>>();
+            let cond = match conds.as_slice() {
+                [cond] => quote!(#cond),
+                _ => quote!(any(#(#conds),*)),
+            };
+            let path = &attr.path;
+            let tokens = &attr.tokens;
+            attrs.push(syn::parse_quote!(#[cfg_attr(#cond, #path #tokens)]));
+        }
+
+        if let Some(item_attrs) = item_attrs_mut(&mut item) {
+            *item_attrs = attrs;
+        }
+        merged.push(item);
+    }
+
+    Ok(quote!(#(#merged)*).into())
+}
+
+/// The item without its own attributes, for comparison.
+fn without_attrs(item: &Item) -> String {
+    let mut item = item.clone();
+    if let Some(attrs) = item_attrs_mut(&mut item) {
+        attrs.clear();
+    }
+    item.to_token_stream().to_string()
+}
+
+fn item_attrs_mut(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    match item {
+        Item::Const(item) => Some(&mut item.attrs),
+        Item::Enum(item) => Some(&mut item.attrs),
+        Item::ExternCrate(item) => Some(&mut item.attrs),
+        Item::Fn(item) => Some(&mut item.attrs),
+        Item::ForeignMod(item) => Some(&mut item.attrs),
+        Item::Impl(item) => Some(&mut item.attrs),
+        Item::Macro(item) => Some(&mut item.attrs),
+        Item::Macro2(item) => Some(&mut item.attrs),
+        Item::Mod(item) => Some(&mut item.attrs),
+        Item::Static(item) => Some(&mut item.attrs),
+        Item::Struct(item) => Some(&mut item.attrs),
+        Item::Trait(item) => Some(&mut item.attrs),
+        Item::TraitAlias(item) => Some(&mut item.attrs),
+        Item::Type(item) => Some(&mut item.attrs),
+        Item::Union(item) => Some(&mut item.attrs),
+        Item::Use(item) => Some(&mut item.attrs),
+        _ => None,
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
 pub fn convert(mut params: MacroParameters, input: TokenStream, convert_mode: ConvertMode) -> TokenStream {
     dump_tokens!("convert before", &input);
 
     let mut file = parse_macro_input!(input as File);
-    for item in &mut file.items {
-        match item {
+    let mut items = Vec::with_capacity(file.items.len());
+    let mut reports = vec![];
+    for mut item in file.items.drain(..) {
+        items.extend(unwrap_or_error!(take_raw_items(&params, &mut item, convert_mode)));
+
+        let original = item_name(&item).cloned();
+        let report = match &mut item {
             syn::Item::Impl(item) => convert_impl(&mut params, item, convert_mode),
             syn::Item::Struct(item) => convert_struct(&mut params, item, convert_mode),
             syn::Item::Enum(item) => convert_enum(&mut params, item, convert_mode),
             syn::Item::Trait(item) => convert_trait(&mut params, item, convert_mode),
             syn::Item::Fn(item) => convert_fn(&mut params, item, convert_mode),
             syn::Item::Use(item) => convert_use(&mut params, item, convert_mode),
+            syn::Item::Const(item) => convert_const(&mut params, item, convert_mode),
+            syn::Item::Static(item) => convert_static(&mut params, item, convert_mode),
+            syn::Item::Type(item) => convert_type_alias(&mut params, item, convert_mode),
+            syn::Item::Macro(item) if item.ident.is_some() => convert_macro_rules(&mut params, item, convert_mode),
+            _ if params.keep_unsupported_get() => {
+                // `emit_error!` already collects the error and lets the other items convert, so this
+                // stands in for a separate non-aborting entry point; keep the item as is, so that its
+                // uses don't produce more errors
+                emit_error!(item.span(), "Allowed impl, struct, enum, trait, fn, use, const, static, type or macro_rules items only");
+                None
+            }
             _ => {
-                abort!(item.span(), "Allowed impl, struct, enum, trait, fn or use items only");
+                abort!(item.span(), "Allowed impl, struct, enum, trait, fn, use, const, static, type or macro_rules items only");
             }
-        }
+        };
+        reports.extend(report);
+        syn::visit_mut::visit_item_mut(&mut OurAttrsCleaner { params: &params }, &mut item);
+        let reexport = match (params.reexport_get(), original, item_name(&item)) {
+            (true, Some(original), Some(name)) if original != *name => Some(make_reexport(&item, name, &original)),
+            _ => None,
+        };
+        items.push(item);
+        items.extend(reexport);
     }
-    let ts = quote!(#file);
+    file.items = items;
+    let mut ts = quote!(#file);
 
     dump_tokens2!("convert after", &ts);
+    if params.dump_get() {
+        let name = file.items.iter().filter_map(item_name).next().map(|name| name.to_string());
+        let warning = dump_converted(name.as_deref().unwrap_or("item"), convert_mode.to_str(), &reports, &ts);
+        ts.extend(warning);
+    }
     ts.into()
 }
 
-fn convert_impl(params: &mut MacroParameters, item: &mut ItemImpl, convert_mode: ConvertMode) {
+/// Removes leftover attributes of the crate (`#[maybe_async_cfg::...]`) from fields, variants and
+/// function arguments of the converted item, where attribute macros can't be expanded. Nested
+/// items are skipped: they are expanded on their own.
+struct OurAttrsCleaner<'p> {
+    params: &'p MacroParameters,
+}
+
+impl<'p> OurAttrsCleaner<'p> {
+    fn clean(&self, attrs: &mut Vec<Attribute>) {
+        attrs.retain(|attr| self.params.is_our_attr(attr).is_none());
+    }
+}
+
+impl<'p> VisitMut for OurAttrsCleaner<'p> {
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+
+    fn visit_field_mut(&mut self, node: &mut syn::Field) {
+        self.clean(&mut node.attrs);
+        syn::visit_mut::visit_field_mut(self, node);
+    }
+
+    fn visit_variant_mut(&mut self, node: &mut syn::Variant) {
+        self.clean(&mut node.attrs);
+        syn::visit_mut::visit_variant_mut(self, node);
+    }
+
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        match node {
+            FnArg::Receiver(receiver) => self.clean(&mut receiver.attrs),
+            FnArg::Typed(typed) => self.clean(&mut typed.attrs),
+        }
+        syn::visit_mut::visit_fn_arg_mut(self, node);
+    }
+}
+
+fn item_name(item: &Item) -> Option<&Ident> {
+    match item {
+        Item::Struct(item) => Some(&item.ident),
+        Item::Enum(item) => Some(&item.ident),
+        Item::Trait(item) => Some(&item.ident),
+        Item::Fn(item) => Some(&item.sig.ident),
+        Item::Const(item) => Some(&item.ident),
+        Item::Static(item) => Some(&item.ident),
+        Item::Type(item) => Some(&item.ident),
+        Item::Macro(item) => item.ident.as_ref(),
+        _ => None,
+    }
+}
+
+/// `pub use self::FooSync as Foo;` with the visibility of the converted item.
+fn make_reexport(item: &Item, name: &Ident, original: &Ident) -> Item {
+    let vis = match item {
+        Item::Struct(item) => &item.vis,
+        Item::Enum(item) => &item.vis,
+        Item::Trait(item) => &item.vis,
+        Item::Fn(item) => &item.vis,
+        Item::Const(item) => &item.vis,
+        Item::Static(item) => &item.vis,
+        Item::Type(item) => &item.vis,
+        _ => unreachable!(),
+    };
+
+    syn::parse_quote!(#vis use self::#name as #original;)
+}
+
+/// Removes `#[maybe_async_cfg::raw(sync = "...", async = "...")]` attributes from the item and
+/// returns the items given for the current version.
+fn take_raw_items(
+    params: &MacroParameters,
+    item: &mut Item,
+    convert_mode: ConvertMode,
+) -> syn::Result<Vec<Item>> {
+    let attrs = match item_attrs_mut(item) {
+        Some(attrs) => attrs,
+        None => return Ok(vec![]),
+    };
+
+    let mut items = vec![];
+    let mut raw_attrs = vec![];
+    for attr in std::mem::take(attrs) {
+        match params.is_our_attr(&attr) {
+            Some(name) if name == MACRO_RAW_NAME => raw_attrs.push(attr),
+            _ => attrs.push(attr),
+        }
+    }
+
+    for attr in raw_attrs {
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => {
+                return Err(syn::Error::new_spanned(meta, "Expected `raw(sync = \"...\", async = \"...\")`"))
+            }
+        };
+        for nm in nested {
+            let (key, value) = match nm {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(value), .. })) => {
+                    match path.get_ident() {
+                        Some(key) if key == "sync" || key == "async" => (key.to_string(), value),
+                        _ => return Err(syn::Error::new_spanned(path, "Expected `sync` or `async`")),
+                    }
+                }
+                nm => return Err(syn::Error::new_spanned(nm, "Expected `sync = \"...\"` or `async = \"...\"`")),
+            };
+            if key != convert_mode.to_str() {
+                continue;
+            }
+            let file = syn::parse_str::<File>(&value.value()).map_err(|err| {
+                syn::Error::new_spanned(&value, format!("Raw tokens must be items: {}", err))
+            })?;
+            items.extend(file.items);
+        }
+    }
+
+    Ok(items)
+}
+
+fn convert_impl(params: &mut MacroParameters, item: &mut ItemImpl, convert_mode: ConvertMode) -> Option<ConversionReport> {
     match &mut *item.self_ty {
         Type::Path(TypePath { path, .. }) => {
             if let Some(last) = path.segments.last_mut() {
@@ -106,90 +442,396 @@ fn convert_impl(params: &mut MacroParameters, item: &mut ItemImpl, convert_mode:
     };
 
     let send = params.send_get();
+    // `async_trait_cfg` requests async_trait (Send by default) under a condition
+    let async_trait_cfg = params.async_trait_cfg_get().map(|cond| cond.to_token_stream().to_string());
+    let send = match (send, &async_trait_cfg) {
+        (None, Some(_)) => Some(true),
+        _ => send,
+    };
+    let name = item.self_ty.to_token_stream().to_string();
 
+    let backend = params.async_trait_get();
+    let keep_asyncness = params.keep_asyncness_get();
     let mut visitor = Visitor::new(AsyncAwaitVisitor::new(params, convert_mode));
 
     match convert_mode {
         ConvertMode::IntoSync => {
-            for inner in &mut item.items {
-                if let ImplItem::Method(ref mut method) = inner {
-                    if method.sig.asyncness.is_some() {
-                        method.sig.asyncness = None;
+            if !keep_asyncness {
+                for inner in &mut item.items {
+                    if let ImplItem::Method(ref mut method) = inner {
+                        if method.sig.asyncness.is_some() {
+                            method.sig.asyncness = None;
+                        }
                     }
                 }
             }
         }
         ConvertMode::IntoAsync => {
-            if let Some(send) = send {
+            // a marker impl without async methods does not need async_trait
+            let has_async_methods = item.items.iter().any(|inner| {
+                matches!(inner, ImplItem::Method(method) if method.sig.asyncness.is_some())
+            });
+
+            // native `async fn` in traits (or `trait_variant`): nothing to add
+            let async_trait = backend == AsyncTraitBackend::AsyncTrait;
+
+            if let (Some(send), true, true) = (send, has_async_methods, async_trait) {
                 let attr_str = if send {
                     "async_trait::async_trait"
                 } else {
                     "async_trait::async_trait(?Send)"
                 };
+                let attr_str = match &async_trait_cfg {
+                    Some(cond) => format!("cfg_attr({}, {})", cond, attr_str),
+                    None => attr_str.to_string(),
+                };
                 let attr = make_attr_from_str(attr_str, item.span()).unwrap();
                 item.attrs.push(attr);
             }
         }
     }
 
-    visitor.visit_item_impl_mut(item)
+    visitor.visit_item_impl_mut(item);
+    let report = visitor.inner.write_report(name);
+
+    add_attrs(params, &mut item.attrs, convert_mode);
+
+    report
 }
 
-fn convert_struct(params: &mut MacroParameters, item: &mut ItemStruct, convert_mode: ConvertMode) {
-    params.original_self_name_set(item.ident.to_string(), false);
+/// Appends attributes of `add_attrs` for the current mode.
+fn add_attrs(params: &MacroParameters, attrs: &mut Vec<Attribute>, convert_mode: ConvertMode) {
+    match params.add_attrs_get(convert_mode) {
+        Ok(added) => attrs.extend(added),
+        Err(err) => emit_error!(err.span(), "{}", err),
+    }
+}
+
+/// Replaces the visibility of the item with the one of `vis` for the current mode.
+fn set_vis(params: &MacroParameters, vis: &mut Visibility, convert_mode: ConvertMode) {
+    if let Some(new_vis) = params.vis_get(convert_mode) {
+        *vis = new_vis;
+    }
+}
+
+fn convert_struct(params: &mut MacroParameters, item: &mut ItemStruct, convert_mode: ConvertMode) -> Option<ConversionReport> {
+    let name = item.ident.to_string();
+    params.original_self_name_set(&name, false);
+
+    if params.drop_pin_get(convert_mode) {
+        drop_pin_attrs(&mut item.attrs);
+        drop_pin_field_attrs(&mut item.fields);
+    }
 
     let mut visitor = Visitor::new(AsyncAwaitVisitor::new(params, convert_mode));
-    visitor.visit_item_struct_mut(item)
+    visitor.visit_item_struct_mut(item);
+    let report = visitor.inner.write_report(name);
+
+    add_attrs(params, &mut item.attrs, convert_mode);
+    set_vis(params, &mut item.vis, convert_mode);
+
+    report
+}
+
+/// Removes `#[pin_project]` (and `#[pin_project::pin_project(...)]`).
+fn drop_pin_attrs(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| {
+        !matches!(attr.path.segments.last(), Some(segment) if segment.ident == "pin_project")
+    });
+}
+
+/// Removes `#[pin]` on fields.
+fn drop_pin_field_attrs(fields: &mut Fields) {
+    for field in fields.iter_mut() {
+        field.attrs.retain(|attr| !attr.path.is_ident("pin"));
+    }
 }
 
-fn convert_enum(params: &mut MacroParameters, item: &mut ItemEnum, convert_mode: ConvertMode) {
-    params.original_self_name_set(item.ident.to_string(), false);
+fn convert_enum(params: &mut MacroParameters, item: &mut ItemEnum, convert_mode: ConvertMode) -> Option<ConversionReport> {
+    let name = item.ident.to_string();
+    params.original_self_name_set(&name, false);
+
+    if params.drop_pin_get(convert_mode) {
+        drop_pin_attrs(&mut item.attrs);
+        for variant in &mut item.variants {
+            drop_pin_field_attrs(&mut variant.fields);
+        }
+    }
 
     let mut visitor = Visitor::new(AsyncAwaitVisitor::new(params, convert_mode));
-    visitor.visit_item_enum_mut(item)
+    visitor.visit_item_enum_mut(item);
+    let report = visitor.inner.write_report(name);
+
+    add_attrs(params, &mut item.attrs, convert_mode);
+    set_vis(params, &mut item.vis, convert_mode);
+
+    report
 }
 
-fn convert_trait(params: &mut MacroParameters, item: &mut ItemTrait, convert_mode: ConvertMode) {
-    params.original_self_name_set(item.ident.to_string(), false);
+fn convert_trait(params: &mut MacroParameters, item: &mut ItemTrait, convert_mode: ConvertMode) -> Option<ConversionReport> {
+    let name = item.ident.to_string();
+    params.original_self_name_set(&name, false);
 
+    let backend = params.async_trait_get();
+    let send = params.send_get();
+    let keep_asyncness = params.keep_asyncness_get();
     let mut visitor = Visitor::new(AsyncAwaitVisitor::new(params, convert_mode));
 
     match convert_mode {
         ConvertMode::IntoSync => {
-            for inner in &mut item.items {
-                if let TraitItem::Method(ref mut method) = inner {
-                    if method.sig.asyncness.is_some() {
-                        method.sig.asyncness = None;
+            if !keep_asyncness {
+                for inner in &mut item.items {
+                    if let TraitItem::Method(ref mut method) = inner {
+                        if method.sig.asyncness.is_some() {
+                            method.sig.asyncness = None;
+                        }
                     }
                 }
             }
         }
-        ConvertMode::IntoAsync => {}
+        ConvertMode::IntoAsync => {
+            // `async fn` can't require `Send` futures, so it's desugared
+            if backend == AsyncTraitBackend::Native && send == Some(true) {
+                for inner in &mut item.items {
+                    if let TraitItem::Method(ref mut method) = inner {
+                        if method.sig.asyncness.is_some() {
+                            make_send_future_method(method);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    visitor.visit_item_trait_mut(item);
+    let report = visitor.inner.write_report(name);
+    add_attrs(params, &mut item.attrs, convert_mode);
+    set_vis(params, &mut item.vis, convert_mode);
+
+    // `#[trait_variant::make(Send)] trait FooAsync`: the trait is rewritten in place, its async
+    // methods return `Send` futures (or any futures with `?Send`)
+    if let (ConvertMode::IntoAsync, AsyncTraitBackend::TraitVariant, Some(send)) = (convert_mode, backend, send) {
+        if send {
+            item.attrs.push(syn::parse_quote!(#[trait_variant::make(Send)]));
+        } else {
+            item.attrs.push(syn::parse_quote!(#[trait_variant::make()]));
+        }
     }
 
-    visitor.visit_item_trait_mut(item)
+    report
+}
+
+/// `async fn f(&self) -> T { body }` -> `fn f(&self) -> impl Future<Output = T> + Send { async move { body } }`
+fn make_send_future_method(method: &mut TraitItemMethod) {
+    method.sig.asyncness = None;
+
+    let output = match &method.sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, ty) => ty.to_token_stream(),
+    };
+    method.sig.output =
+        syn::parse_quote!(-> impl ::core::future::Future<Output = #output> + ::core::marker::Send);
+
+    if let Some(block) = &mut method.default {
+        let stmts = &block.stmts;
+        *block = syn::parse_quote!({ async move { #(#stmts)* } });
+
+        // the future holds the receiver, like in `async_trait`
+        let bound: Option<syn::WherePredicate> = match method.sig.receiver() {
+            Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() && receiver.mutability.is_none() => {
+                Some(syn::parse_quote!(Self: ::core::marker::Sync))
+            }
+            Some(_) => Some(syn::parse_quote!(Self: ::core::marker::Send)),
+            None => None,
+        };
+        if let Some(bound) = bound {
+            method.sig.generics.make_where_clause().predicates.push(bound);
+        }
+    }
 }
 
-fn convert_fn(params: &mut MacroParameters, item: &mut ItemFn, convert_mode: ConvertMode) {
-    params.original_self_name_set(item.sig.ident.to_string(), true);
+fn convert_fn(params: &mut MacroParameters, item: &mut ItemFn, convert_mode: ConvertMode) -> Option<ConversionReport> {
+    let name = item.sig.ident.to_string();
+    params.original_self_name_set(&name, true);
+
+    let keep_asyncness = params.keep_asyncness_get();
+    let keep_runtime_attrs = params.keep_runtime_attrs_get();
+    let block_on = match convert_mode {
+        ConvertMode::IntoSync if item.sig.asyncness.is_some() => {
+            params.sync_via_block_on_get().map(make_path)
+        }
+        _ => None,
+    };
+
+    // `sync_via_block_on`: the body stays async (converted as in the async version) and runs on
+    // the executor
+    if let Some(block_on) = &block_on {
+        let mut block = std::mem::replace(&mut *item.block, syn::parse_quote!({}));
+        let mut visitor = Visitor::new(AsyncAwaitVisitor::new(params, ConvertMode::IntoAsync));
+        visitor.visit_block_mut(&mut block);
+        let stmts = &block.stmts;
+        *item.block = syn::parse_quote!({ #block_on(async { #(#stmts)* }) });
+    }
 
     let mut visitor = Visitor::new(AsyncAwaitVisitor::new(params, convert_mode));
 
     match convert_mode {
         ConvertMode::IntoSync => {
-            if item.sig.asyncness.is_some() {
+            if item.sig.asyncness.is_some() && !keep_asyncness {
                 item.sig.asyncness = None;
+                // `#[tokio::main] async fn main()` -> `fn main()`
+                if !keep_runtime_attrs {
+                    item.attrs.retain(|attr| !RUNTIME_ATTRS.contains(&attr_path_to_string(&attr.path).as_str()));
+                }
             }
         }
         ConvertMode::IntoAsync => {}
     }
 
-    visitor.visit_item_fn_mut(item)
+    if block_on.is_some() {
+        // the body is already converted
+        let block = std::mem::replace(&mut *item.block, syn::parse_quote!({}));
+        visitor.visit_item_fn_mut(item);
+        *item.block = block;
+    } else {
+        visitor.visit_item_fn_mut(item);
+    }
+    let report = visitor.inner.write_report(name);
+
+    add_attrs(params, &mut item.attrs, convert_mode);
+    set_vis(params, &mut item.vis, convert_mode);
+
+    report
+}
+
+fn convert_type_alias(params: &mut MacroParameters, item: &mut ItemType, convert_mode: ConvertMode) -> Option<ConversionReport> {
+    let name = item.ident.to_string();
+    params.original_self_name_set(&name, false);
+
+    let mut visitor = Visitor::new(AsyncAwaitVisitor::new(params, convert_mode));
+    visitor.visit_item_type_mut(item);
+    let report = visitor.inner.write_report(name);
+
+    add_attrs(params, &mut item.attrs, convert_mode);
+    set_vis(params, &mut item.vis, convert_mode);
+
+    report
+}
+
+/// `macro_rules!` definition: the macro is renamed, its body is kept as is unless
+/// `rewrite_macro_body` is set.
+fn convert_macro_rules(params: &mut MacroParameters, item: &mut ItemMacro, convert_mode: ConvertMode) -> Option<ConversionReport> {
+    let name = item.ident.as_ref().map(|ident| ident.to_string()).unwrap_or_default();
+    params.original_self_name_set(&name, true);
+
+    let rewrite_body = params.rewrite_macro_body_get();
+    let mut visitor = Visitor::new(AsyncAwaitVisitor::new(params, convert_mode));
+    visitor.visit_item_macro_mut(item);
+    if rewrite_body {
+        let tokens = std::mem::take(&mut item.mac.tokens);
+        item.mac.tokens = visitor.inner.process_macro_rules(tokens);
+    }
+    let report = visitor.inner.write_report(name);
+
+    add_attrs(params, &mut item.attrs, convert_mode);
+
+    report
+}
+
+fn convert_const(params: &mut MacroParameters, item: &mut ItemConst, convert_mode: ConvertMode) -> Option<ConversionReport> {
+    let name = item.ident.to_string();
+    params.original_self_name_set(&name, true);
+
+    let mut visitor = Visitor::new(AsyncAwaitVisitor::new(params, convert_mode));
+    visitor.visit_item_const_mut(item);
+    let report = visitor.inner.write_report(name);
+
+    add_attrs(params, &mut item.attrs, convert_mode);
+    set_vis(params, &mut item.vis, convert_mode);
+
+    report
+}
+
+fn convert_static(params: &mut MacroParameters, item: &mut ItemStatic, convert_mode: ConvertMode) -> Option<ConversionReport> {
+    let name = item.ident.to_string();
+    params.original_self_name_set(&name, true);
+
+    let mut visitor = Visitor::new(AsyncAwaitVisitor::new(params, convert_mode));
+    visitor.visit_item_static_mut(item);
+    let report = visitor.inner.write_report(name);
+
+    add_attrs(params, &mut item.attrs, convert_mode);
+    set_vis(params, &mut item.vis, convert_mode);
+
+    report
 }
 
-fn convert_use(params: &mut MacroParameters, item: &mut ItemUse, convert_mode: ConvertMode) {
+fn convert_use(params: &mut MacroParameters, item: &mut ItemUse, convert_mode: ConvertMode) -> Option<ConversionReport> {
+    let name = item.tree.to_token_stream().to_string();
+
     let mut visitor = Visitor::new(AsyncAwaitVisitor::new(params, convert_mode));
-    visitor.visit_item_use_mut(item)
+    visitor.visit_item_use_mut(item);
+    let report = visitor.inner.write_report(name);
+
+    add_attrs(params, &mut item.attrs, convert_mode);
+    set_vis(params, &mut item.vis, convert_mode);
+
+    report
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub fn define(body: TokenStream) -> TokenStream {
+    dump_tokens!("define", &body);
+
+    let def = parse_macro_input!(body as TemplateDefinition);
+    let name = &def.name;
+    let args = def.args.iter();
+
+    // `maybe` with `use = "name"` calls the template as `name! { [maybe path] (args before)
+    // (args after) item }`, the parameters of the template are inserted between them
+    let ts = quote!(
+        #[allow(unused_macros)]
+        macro_rules! #name {
+            ([$maybe:path] ($($before:tt)*) ($($after:tt)*) $($item:tt)*) => {
+                #[$maybe($($before)* #(#args,)* $($after)*)]
+                $($item)*
+            };
+        }
+    );
+
+    dump_tokens!("define after", &ts);
+    ts.into()
+}
+
+/// Replaces the first `use = "name"` in `maybe` parameters with a call of the template macro
+/// generated by `define!`. Other templates are expanded by the `maybe` produced by the call.
+fn expand_template(args: TokenStream, input: TokenStream) -> syn::Result<Option<TokenStream>> {
+    let list = syn::parse_macro_input::parse::<AttributeArgs>(args)?;
+
+    let pos = match list.iter().position(is_template_use) {
+        Some(pos) => pos,
+        None => return Ok(None),
+    };
+    let name = match &list[pos] {
+        NestedMeta::Meta(Meta::NameValue(MetaNameValue { lit: Lit::Str(lit), .. })) => lit.parse::<Ident>()?,
+        _ => unreachable!(),
+    };
+
+    let (before, after) = (&list[..pos], &list[pos + 1..]);
+    let rest = before.iter().chain(after).filter(|nm| !is_template_use(nm));
+    let params = MacroParameters::from_tokens(quote!(#(#rest),*).into())?;
+    let maybe = params.make_self_path(MACRO_MAYBE_NAME);
+
+    let input = TokenStream2::from(input);
+    Ok(Some(quote!(#name! { [#maybe] (#(#before,)*) (#(#after,)*) #input }).into()))
+}
+
+fn is_template_use(nm: &NestedMeta) -> bool {
+    matches!(
+        nm,
+        NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(_), .. })) if path.is_ident("use")
+    )
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
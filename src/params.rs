@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 #[allow(unused_imports)]
 use std::iter::FromIterator;
 
@@ -11,12 +11,25 @@ use syn::{
 };
 
 use crate::{
-    DEFAULT_CRATE_NAME, STANDARD_MACROS,
+    DEFAULT_CRATE_NAME, MACRO_MAYBE_NAME, STANDARD_MACROS,
     utils::*,
 };
 
-const MODE_INTO_ASYNC: &'static str = "__into_async";
-const MODE_INTO_SYNC: &'static str = "__into_sync";
+const MODE_INTO_ASYNC: &str = "__into_async";
+const MODE_INTO_SYNC: &str = "__into_sync";
+
+/// Parameter names checked for typos in `strict_params` mode.
+const KNOWN_PARAMS: &[&str] = &[
+    "disable", "force", "keep_self", "key_cfg", "trim_cfg", "keep_asyncness", "keep_runtime_attrs", "reexport", "rewrite_macro_body", "strip_macro_awaits", "rewrite_spawn", "rewrite_sleep", "rewrite_boxed", "check_features", "conditional_attrs", "dump", "keep_unsupported", "strict_params", "key", "self", "as_name", "prefix", "macro_path", "send", "async_trait", "drop_pin", "feature",
+    "rewrite_stream", "sync_via_block_on", "async_trait_cfg", "cfg", "idents", "outer", "inner", "add_attrs", "replace_feature", "drop_attrs",
+    "drop_args", "drop_bounds", "transparent_macros", "rename_crate", "auto_cfg", "vis", "replace_ident", "replace_path", "methods", "use", "sync", "async",
+];
+
+/// Attribute names that are never reported as typos in `strict_params` mode.
+const KNOWN_ATTRS: &[&str] = &[
+    "test", "ignore", "should_panic", "inline", "cold", "must_use", "allow", "warn", "deny",
+    "forbid", "doc", "derive", "track_caller", "non_exhaustive", "deprecated",
+];
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -35,7 +48,7 @@ impl ConvertMode {
         }
     }
 
-    fn to_str(&self) -> &'static str {
+    pub fn to_str(self) -> &'static str {
         match self {
             Self::IntoSync => "sync",
             Self::IntoAsync => "async",
@@ -45,14 +58,172 @@ impl ConvertMode {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// How async methods of traits and impls are compiled in the async version (`async_trait = "..."`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AsyncTraitBackend {
+    /// `#[async_trait::async_trait]` is added to impls when `send` is set
+    AsyncTrait,
+    /// `async fn` in traits is left as is, `send = "Send"` requires `Send` futures in the trait
+    Native,
+    /// like `Native`, `send` adds `trait_variant::make(Send)` (or `make()` for `?Send`)
+    TraitVariant,
+}
+
+impl AsyncTraitBackend {
+    fn from_str<S: AsRef<str>>(s: S) -> Option<Self> {
+        match s.as_ref() {
+            "async_trait" => Some(Self::AsyncTrait),
+            "native" => Some(Self::Native),
+            "trait_variant" => Some(Self::TraitVariant),
+            _ => None,
+        }
+    }
+
+    fn to_str(self) -> &'static str {
+        match self {
+            Self::AsyncTrait => "async_trait",
+            Self::Native => "native",
+            Self::TraitVariant => "trait_variant",
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Replacement of `replace_feature("name", ...)` in `cfg` conditions.
+#[derive(Clone)]
+pub enum FeatureReplacement {
+    /// `"other"`: `feature = "other"`
+    Feature(String),
+    /// `all(feature = "x", not(feature = "y"))`: the whole predicate
+    Predicate(Meta),
+    /// `""`: the condition is removed (always true)
+    Removed,
+}
+
+impl FeatureReplacement {
+    fn to_nestedmeta(&self) -> NestedMeta {
+        match self {
+            Self::Feature(name) => NestedMeta::Lit(Lit::Str(LitStr::new(name, Span::call_site()))),
+            Self::Predicate(meta) => NestedMeta::Meta(meta.clone()),
+            Self::Removed => NestedMeta::Lit(Lit::Str(LitStr::new("", Span::call_site()))),
+        }
+    }
+}
+
+impl std::fmt::Display for FeatureReplacement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Feature(name) => write!(f, "{}", name),
+            Self::Predicate(meta) => write!(f, "{}", meta.to_token_stream()),
+            Self::Removed => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Debug for FeatureReplacement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.to_string())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Entry of `drop_attrs`: an attribute path (`attr`, `tokio::test`) or a path prefix (`"tokio::*"`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttrMatcher {
+    Path(String),
+    Prefix(String),
+}
+
+impl AttrMatcher {
+    fn from_str(s: &str) -> Self {
+        let s = s.trim().trim_start_matches("::");
+        match s.strip_suffix("::*") {
+            Some(prefix) => Self::Prefix(prefix.to_string()),
+            None => Self::Path(s.to_string()),
+        }
+    }
+
+    /// `path` is an attribute path with segments joined by `::`
+    pub fn matches(&self, path: &str) -> bool {
+        match self {
+            Self::Path(name) => name == path,
+            Self::Prefix(prefix) => path
+                .strip_prefix(prefix.as_str())
+                .is_some_and(|rest| rest.starts_with("::")),
+        }
+    }
+
+    fn to_nestedmeta(&self) -> NestedMeta {
+        match self {
+            Self::Path(name) => NestedMeta::Meta(Meta::Path(make_path(name))),
+            Self::Prefix(prefix) => NestedMeta::Lit(Lit::Str(LitStr::new(
+                &format!("{}::*", prefix),
+                Span::call_site(),
+            ))),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Value for each version (`None` keeps the original): feature of `auto_cfg`, visibility of `vis`.
+#[derive(Debug, Clone, Default)]
+pub struct ReplaceRecord {
+    pub value_sync: Option<String>,
+    pub value_async: Option<String>,
+}
+
+impl ReplaceRecord {
+    pub fn get(&self, convert_mode: ConvertMode) -> Option<&str> {
+        match convert_mode {
+            ConvertMode::IntoSync => self.value_sync.as_deref(),
+            ConvertMode::IntoAsync => self.value_async.as_deref(),
+        }
+    }
+}
+
+/// Replacement path of `replace_ident` and `replace_path` for each version (`None` keeps the
+/// original), parsed once with the parameters.
+#[derive(Debug, Clone, Default)]
+pub struct PathRecord {
+    pub path_sync: Option<syn::Path>,
+    pub path_async: Option<syn::Path>,
+}
+
+impl PathRecord {
+    pub fn get(&self, convert_mode: ConvertMode) -> Option<&syn::Path> {
+        match convert_mode {
+            ConvertMode::IntoSync => self.path_sync.as_ref(),
+            ConvertMode::IntoAsync => self.path_async.as_ref(),
+        }
+    }
+
+    fn to_nestedmeta(&self, name: &str, key: &str) -> NestedMeta {
+        let mut nested = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
+        nested.push(NestedMeta::Lit(Lit::Str(LitStr::new(name, Span::call_site()))));
+        if let Some(path) = &self.path_sync {
+            nested.push(make_nestedmeta_namevalue("sync", &path.to_token_stream().to_string()));
+        }
+        if let Some(path) = &self.path_async {
+            nested.push(make_nestedmeta_namevalue("async", &path.to_token_stream().to_string()));
+        }
+        make_nestedmeta_list(key, nested)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
 #[derive(Debug, Clone)]
 pub struct IdentRecord {
     pub fn_mode: bool,
     pub use_mode: bool,
+    pub method_mode: bool,
     pub keep: bool,
     pub ident_sync: Option<String>,
     pub ident_async: Option<String>,
-    pub idents: Option<HashMap<String, String>>,
+    pub idents: Option<BTreeMap<String, String>>,
 }
 
 impl IdentRecord {
@@ -60,6 +231,7 @@ impl IdentRecord {
         Self {
             fn_mode: false,
             use_mode: false,
+            method_mode: false,
             keep: false,
             ident_sync: None,
             ident_async: None,
@@ -71,6 +243,7 @@ impl IdentRecord {
         Self {
             fn_mode,
             use_mode: false,
+            method_mode: false,
             keep: false,
             ident_sync: None,
             ident_async: None,
@@ -104,11 +277,16 @@ impl IdentRecord {
             }
         };
 
-        let suffix = match (self.fn_mode, convert_mode) {
-            (false, ConvertMode::IntoAsync) => "Async",
-            (false, ConvertMode::IntoSync) => "Sync",
-            (true, ConvertMode::IntoAsync) => "_async",
-            (true, ConvertMode::IntoSync) => "_sync",
+        // `const` and `static` items are named in upper case, keep their names in it
+        let name = ident.to_string();
+        let upper_case = name.chars().any(|c| c.is_ascii_uppercase()) && !name.chars().any(|c| c.is_ascii_lowercase());
+        let suffix = match (self.fn_mode, upper_case, convert_mode) {
+            (false, _, ConvertMode::IntoAsync) => "Async",
+            (false, _, ConvertMode::IntoSync) => "Sync",
+            (true, false, ConvertMode::IntoAsync) => "_async",
+            (true, false, ConvertMode::IntoSync) => "_sync",
+            (true, true, ConvertMode::IntoAsync) => "_ASYNC",
+            (true, true, ConvertMode::IntoSync) => "_SYNC",
         };
 
         Ident::new(&format!("{}{}", ident, suffix), ident.span())
@@ -124,6 +302,10 @@ impl IdentRecord {
         if self.use_mode {
             nested.push(syn::NestedMeta::Meta(syn::Meta::Path(make_path("use"))));
         };
+
+        if self.method_mode {
+            nested.push(syn::NestedMeta::Meta(syn::Meta::Path(make_path("method"))));
+        };
     
         if self.keep {
             nested.push(syn::NestedMeta::Meta(syn::Meta::Path(make_path("keep"))));
@@ -170,19 +352,55 @@ pub struct MacroParameterVersion {
 pub struct MacroParameters {
     mode: Option<ConvertMode>,
     disable: bool,
+    force: bool,
     key: Option<String>,
     self_name: Option<String>,
+    // name of the converted item, set with `original_self_name_set` (not a parameter)
+    original_self_name: Option<String>,
+    as_name: Option<String>,
     keep_self: bool,
+    key_cfg: bool,
+    trim_cfg: bool,
+    keep_asyncness: bool,
+    keep_runtime_attrs: bool,
+    reexport: bool,
+    rewrite_macro_body: bool,
+    strip_macro_awaits: bool,
+    rewrite_spawn: bool,
+    rewrite_sleep: bool,
+    rewrite_boxed: bool,
+    dump: bool,
+    keep_unsupported: bool,
+    check_features: bool,
+    conditional_attrs: bool,
+    strict_params: bool,
     // settings
     prefix: Option<String>,
-    idents: HashMap<String, IdentRecord>,
+    macro_path: Option<String>,
+    idents: BTreeMap<String, IdentRecord>,
     send: Option<bool>,
+    async_trait: Option<AsyncTraitBackend>,
+    drop_pin: Option<bool>,
+    rewrite_stream: Option<String>,
+    sync_via_block_on: Option<String>,
+    async_trait_cfg: Option<Meta>,
     // groups
     cfg: Option<Meta>,
     outer_attrs: Punctuated<NestedMeta, Comma>,
     inner_attrs: Punctuated<NestedMeta, Comma>,
-    drop_attrs: Vec<String>,
-    replace_features: HashMap<String, String>,
+    add_attrs_sync: Punctuated<NestedMeta, Comma>,
+    add_attrs_async: Punctuated<NestedMeta, Comma>,
+    drop_attrs: Vec<AttrMatcher>,
+    drop_args: Vec<String>,
+    transparent_macros: Vec<String>,
+    drop_bounds: Vec<String>,
+    replace_features: BTreeMap<String, FeatureReplacement>,
+    replace_ident: BTreeMap<String, PathRecord>,
+    replace_path: BTreeMap<String, PathRecord>,
+    rename_crate: BTreeMap<String, String>,
+    auto_cfg: ReplaceRecord,
+    vis: ReplaceRecord,
+    methods: BTreeMap<String, String>,
     // versions
     pub versions: Vec<MacroParameterVersion>,
 }
@@ -192,18 +410,53 @@ impl std::fmt::Debug for MacroParameters {
         f.debug_struct("MacroParameters")
            .field("mode", &self.mode)
            .field("disable", &self.disable)
+           .field("force", &self.force)
            .field("key", &self.key)
            .field("self_name", &self.self_name)
+           .field("original_self_name", &self.original_self_name)
+           .field("as_name", &self.as_name)
            .field("prefix", &self.prefix)
+           .field("macro_path", &self.macro_path)
            .field("idents", &self.idents)
            .field("send", &self.send)
+           .field("async_trait", &self.async_trait)
+           .field("drop_pin", &self.drop_pin)
+           .field("rewrite_stream", &self.rewrite_stream)
+           .field("sync_via_block_on", &self.sync_via_block_on)
+           .field("async_trait_cfg", &OptionToTokens(self.async_trait_cfg.as_ref()))
            .field("keep_self", &self.keep_self)
+           .field("key_cfg", &self.key_cfg)
+           .field("trim_cfg", &self.trim_cfg)
+           .field("keep_asyncness", &self.keep_asyncness)
+           .field("keep_runtime_attrs", &self.keep_runtime_attrs)
+           .field("reexport", &self.reexport)
+           .field("rewrite_macro_body", &self.rewrite_macro_body)
+           .field("strip_macro_awaits", &self.strip_macro_awaits)
+           .field("rewrite_spawn", &self.rewrite_spawn)
+           .field("rewrite_sleep", &self.rewrite_sleep)
+           .field("rewrite_boxed", &self.rewrite_boxed)
+           .field("dump", &self.dump)
+           .field("keep_unsupported", &self.keep_unsupported)
+           .field("check_features", &self.check_features)
+           .field("conditional_attrs", &self.conditional_attrs)
+           .field("strict_params", &self.strict_params)
            .field("cfg", &OptionToTokens(self.cfg.as_ref()))
            .field("outer_attrs", &DebugByDisplay(self.outer_attrs.to_token_stream()))
            .field("inner_attrs", &DebugByDisplay(self.inner_attrs.to_token_stream()))
+           .field("add_attrs_sync", &DebugByDisplay(self.add_attrs_sync.to_token_stream()))
+           .field("add_attrs_async", &DebugByDisplay(self.add_attrs_async.to_token_stream()))
            .field("outer_attrs", &DebugByDisplay(self.outer_attrs.to_token_stream()))
            .field("drop_attrs", &self.drop_attrs)
+           .field("drop_args", &self.drop_args)
+           .field("transparent_macros", &self.transparent_macros)
+           .field("drop_bounds", &self.drop_bounds)
            .field("replace_features", &self.replace_features)
+           .field("replace_ident", &self.replace_ident)
+           .field("replace_path", &self.replace_path)
+           .field("rename_crate", &self.rename_crate)
+           .field("auto_cfg", &self.auto_cfg)
+           .field("vis", &self.vis)
+           .field("methods", &self.methods)
            .field("versions", &self.versions)
            .finish()
         }
@@ -236,8 +489,27 @@ impl MacroParameters {
     }
 
     fn from_args<'i>(args: impl IntoIterator<Item = &'i NestedMeta>) -> syn::Result<Self> {
+        Self::from_args_with_strict(args, false)
+    }
+
+    fn from_args_with_strict<'i>(
+        args: impl IntoIterator<Item = &'i NestedMeta>,
+        strict: bool,
+    ) -> syn::Result<Self> {
+        let args = args.into_iter().collect::<Vec<_>>();
         let mut builder = MacroParametersBuilder::new();
 
+        // `strict_params` must be known before any other parameter is checked
+        let strict = strict
+            || args.iter().any(|arg| {
+                matches!(arg, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("strict_params"))
+            });
+        if strict {
+            builder.strict_params();
+        }
+
+        let mut disable_path = None;
+
         for arg in args {
             match arg {
                 syn::NestedMeta::Meta(meta) => match meta {
@@ -252,8 +524,14 @@ impl MacroParameters {
                         match name.as_str() {
                             "key" => lit_str!(lit, builder, key, "Expected string literal"),
                             "self" => lit_str!(lit, builder, self_name, "Expected string literal"),
+                            "as_name" => lit_str!(lit, builder, as_name, "Expected string literal"),
                             "prefix" => lit_str!(lit, builder, prefix, "Expected string literal"),
+                            "macro_path" => lit_str!(lit, builder, macro_path, "Expected string literal"),
                             "send" => lit_str!(lit, builder, send, "Expected string literal"),
+                            "async_trait" => lit_str!(lit, builder, async_trait, "Expected string literal"),
+                            "drop_pin" => lit_str!(lit, builder, drop_pin, "Expected string literal"),
+                            "rewrite_stream" => lit_str!(lit, builder, rewrite_stream, "Expected string literal"),
+                            "sync_via_block_on" => lit_str!(lit, builder, sync_via_block_on, "Expected string literal"),
                             "feature" => lit_meta!(lit, meta, builder, feature, "Expected string literal"),
                             _ => {
                                 return Err(syn::Error::new_spanned(
@@ -274,6 +552,7 @@ impl MacroParameters {
                             .to_string();
                         match name.as_str() {
                             "cfg" => builder.cfg_list(list)?,
+                            "async_trait_cfg" => builder.async_trait_cfg(list)?,
                             "idents" => MacroParametersBuilder::idents(
                                 &mut builder.params.idents,
                                 &list.nested,
@@ -281,8 +560,18 @@ impl MacroParameters {
                             "any" | "all" | "not" => builder.cfg_meta(meta)?,
                             "outer" => builder.outer_attrs(&list.nested)?,
                             "inner" => builder.inner_attrs(&list.nested)?,
+                            "add_attrs" => builder.add_attrs(&list.nested)?,
                             "replace_feature" => builder.replace_feature(&list.nested)?,
                             "drop_attrs" => builder.drop_attrs(&list.nested)?,
+                            "drop_args" => builder.drop_args(&list.nested)?,
+                            "transparent_macros" => builder.transparent_macros(&list.nested)?,
+                            "drop_bounds" => builder.drop_bounds(&list.nested)?,
+                            "rename_crate" => builder.rename_crate(&list.nested)?,
+                            "auto_cfg" => builder.auto_cfg(&list.nested)?,
+                            "vis" => builder.vis(&list.nested)?,
+                            "replace_ident" => builder.replace_ident(&list.nested)?,
+                            "replace_path" => builder.replace_path(&list.nested)?,
+                            "methods" => builder.methods(&list.nested)?,
                             name @ _ => builder.version_or_inner_attr(name, &list.nested, meta)?,
                         }
                     }
@@ -291,9 +580,34 @@ impl MacroParameters {
                             match name.as_str() {
                                 MODE_INTO_ASYNC => builder.mode_into_async()?,
                                 MODE_INTO_SYNC => builder.mode_into_sync()?,
-                                "disable" => builder.disable(),
+                                "disable" => {
+                                    builder.disable();
+                                    disable_path = Some(path);
+                                }
+                                "force" => builder.force(),
                                 "keep_self" => builder.keep_self(),
-                                _ => builder.inner_attr(meta)?,
+                                "key_cfg" => builder.key_cfg(),
+                                "trim_cfg" => builder.trim_cfg(),
+                                "keep_asyncness" => builder.keep_asyncness(),
+                                "keep_runtime_attrs" => builder.keep_runtime_attrs(),
+                                "reexport" => builder.reexport(),
+                                "rewrite_macro_body" => builder.rewrite_macro_body(),
+                                "strip_macro_awaits" => builder.strip_macro_awaits(),
+                                "rewrite_spawn" => builder.rewrite_spawn(),
+                                "rewrite_sleep" => builder.rewrite_sleep(),
+                                "rewrite_boxed" => builder.rewrite_boxed(),
+                                "dump" => builder.dump(),
+                                "keep_unsupported" => builder.keep_unsupported(),
+                                "check_features" => builder.check_features(),
+                                "conditional_attrs" => builder.conditional_attrs(),
+                                "drop_pin" => builder.drop_pin(String::new())?,
+                                "rewrite_stream" => builder.rewrite_stream(String::new())?,
+                                "sync_via_block_on" => builder.sync_via_block_on(String::new())?,
+                                "strict_params" => {}
+                                _ => {
+                                    builder.check_unknown_param(&name, meta)?;
+                                    builder.inner_attr(meta)?
+                                }
                             }
                         } else {
                             builder.inner_attr(meta)?    
@@ -306,6 +620,16 @@ impl MacroParameters {
             }
         }
 
+        // versions would be silently ignored
+        if let Some(path) = disable_path {
+            if !builder.params.force && !builder.params.versions.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "`disable` cannot be combined with versions",
+                ));
+            }
+        }
+
         builder.build()
     }
 
@@ -351,10 +675,74 @@ impl MacroParameters {
             args.push(NestedMeta::Meta(Meta::Path(make_path("disable"))));
         }
 
+        if self.force {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("force"))));
+        }
+
         if self.keep_self {
             args.push(NestedMeta::Meta(Meta::Path(make_path("keep_self"))));
         }
 
+        if self.key_cfg {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("key_cfg"))));
+        }
+
+        if self.trim_cfg {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("trim_cfg"))));
+        }
+
+        if self.keep_asyncness {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("keep_asyncness"))));
+        }
+
+        if self.keep_runtime_attrs {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("keep_runtime_attrs"))));
+        }
+
+        if self.reexport {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("reexport"))));
+        }
+
+        if self.rewrite_macro_body {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("rewrite_macro_body"))));
+        }
+
+        if self.strip_macro_awaits {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("strip_macro_awaits"))));
+        }
+
+        if self.rewrite_spawn {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("rewrite_spawn"))));
+        }
+
+        if self.rewrite_sleep {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("rewrite_sleep"))));
+        }
+
+        if self.rewrite_boxed {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("rewrite_boxed"))));
+        }
+
+        if self.dump {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("dump"))));
+        }
+
+        if self.keep_unsupported {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("keep_unsupported"))));
+        }
+
+        if self.check_features {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("check_features"))));
+        }
+
+        if self.conditional_attrs {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("conditional_attrs"))));
+        }
+
+        if self.strict_params {
+            args.push(NestedMeta::Meta(Meta::Path(make_path("strict_params"))));
+        }
+
         if let Some(key) = &self.key {
             args.push(make_nestedmeta_namevalue("key", key.as_str()));
         }
@@ -363,23 +751,56 @@ impl MacroParameters {
             args.push(make_nestedmeta_namevalue("self", self_name.as_str()));
         }
 
+        if let Some(as_name) = &self.as_name {
+            args.push(make_nestedmeta_namevalue("as_name", as_name.as_str()));
+        }
+
         if let Some(prefix) = &self.prefix {
             args.push(make_nestedmeta_namevalue("prefix", prefix.as_str()));
         }
 
+        if let Some(macro_path) = &self.macro_path {
+            args.push(make_nestedmeta_namevalue("macro_path", macro_path.as_str()));
+        }
+
         if let Some(send) = &self.send {
             args.push(make_nestedmeta_namevalue(
-                "prefix",
+                "send",
                 if *send { "Send" } else { "?Send" },
             ));
         }
 
+        if let Some(async_trait) = self.async_trait {
+            args.push(make_nestedmeta_namevalue("async_trait", async_trait.to_str()));
+        }
+
+        if let Some(drop_pin) = &self.drop_pin {
+            args.push(make_nestedmeta_namevalue(
+                "drop_pin",
+                if *drop_pin { "true" } else { "false" },
+            ));
+        }
+
+        if let Some(rewrite_stream) = &self.rewrite_stream {
+            args.push(make_nestedmeta_namevalue("rewrite_stream", rewrite_stream.as_str()));
+        }
+
+        if let Some(sync_via_block_on) = &self.sync_via_block_on {
+            args.push(make_nestedmeta_namevalue("sync_via_block_on", sync_via_block_on.as_str()));
+        }
+
         if let Some(cfg) = &self.cfg {
             let mut nested = Punctuated::new();
             nested.push(NestedMeta::Meta(cfg.clone()));
             args.push(make_nestedmeta_list("cfg", nested));
         }
 
+        if let Some(cond) = &self.async_trait_cfg {
+            let mut nested = Punctuated::new();
+            nested.push(NestedMeta::Meta(cond.clone()));
+            args.push(make_nestedmeta_list("async_trait_cfg", nested));
+        }
+
         if !self.outer_attrs.is_empty() {
             args.push(make_nestedmeta_list("outer", self.outer_attrs.clone()));
         }
@@ -388,6 +809,17 @@ impl MacroParameters {
             args.push(make_nestedmeta_list("inner", self.inner_attrs.clone()));
         }
 
+        if !self.add_attrs_sync.is_empty() || !self.add_attrs_async.is_empty() {
+            let mut nested = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
+            if !self.add_attrs_sync.is_empty() {
+                nested.push(make_nestedmeta_list("sync", self.add_attrs_sync.clone()));
+            }
+            if !self.add_attrs_async.is_empty() {
+                nested.push(make_nestedmeta_list("async", self.add_attrs_async.clone()));
+            }
+            args.push(make_nestedmeta_list("add_attrs", nested));
+        }
+
         if !self.idents.is_empty() {
             let mut nested = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
             for (name, value) in &self.idents {
@@ -399,29 +831,101 @@ impl MacroParameters {
 
         if !self.drop_attrs.is_empty() {
             let mut nested = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
-            for name in &self.drop_attrs {
-                nested.push(NestedMeta::Meta(Meta::Path(make_path(name.as_str()))));
+            for matcher in self.drop_attrs() {
+                nested.push(matcher.to_nestedmeta());
             }
             let arg = make_nestedmeta_list("drop_attrs", nested);
             args.push(arg);
         }
 
+        if !self.drop_args.is_empty() {
+            let mut nested = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
+            for name in &self.drop_args {
+                nested.push(NestedMeta::Meta(Meta::Path(make_path(name.as_str()))));
+            }
+            let arg = make_nestedmeta_list("drop_args", nested);
+            args.push(arg);
+        }
+
+        if !self.transparent_macros.is_empty() {
+            let mut nested = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
+            for name in &self.transparent_macros {
+                nested.push(NestedMeta::Meta(Meta::Path(make_path(name.as_str()))));
+            }
+            let arg = make_nestedmeta_list("transparent_macros", nested);
+            args.push(arg);
+        }
+
+        if !self.drop_bounds.is_empty() {
+            let mut nested = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
+            for name in &self.drop_bounds {
+                nested.push(NestedMeta::Meta(Meta::Path(make_path(name.as_str()))));
+            }
+            let arg = make_nestedmeta_list("drop_bounds", nested);
+            args.push(arg);
+        }
+
         if !self.replace_features.is_empty() {
-            for (name, value) in &self.replace_features {
+            for (name, value) in self.replace_features_iter() {
                 let mut inner = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
                 inner.push(NestedMeta::Lit(Lit::Str(LitStr::new(
-                    name.as_str(),
-                    Span::call_site(),
-                ))));
-                inner.push(NestedMeta::Lit(Lit::Str(LitStr::new(
-                    value.as_str(),
+                    name,
                     Span::call_site(),
                 ))));
+                inner.push(value.to_nestedmeta());
                 let arg = make_nestedmeta_list("replace_feature", inner);
                 args.push(arg);
             }
         }
 
+        for (name, record) in &self.replace_ident {
+            args.push(record.to_nestedmeta(name, "replace_ident"));
+        }
+
+        for (name, record) in &self.replace_path {
+            args.push(record.to_nestedmeta(name, "replace_path"));
+        }
+
+        if !self.rename_crate.is_empty() {
+            let mut nested = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
+            for (name, value) in &self.rename_crate {
+                nested.push(make_nestedmeta_namevalue(name.as_str(), value.as_str()));
+            }
+            let arg = make_nestedmeta_list("rename_crate", nested);
+            args.push(arg);
+        }
+
+        if self.auto_cfg.value_sync.is_some() || self.auto_cfg.value_async.is_some() {
+            let mut nested = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
+            if let Some(feature) = &self.auto_cfg.value_sync {
+                nested.push(make_nestedmeta_namevalue("sync", feature));
+            }
+            if let Some(feature) = &self.auto_cfg.value_async {
+                nested.push(make_nestedmeta_namevalue("async", feature));
+            }
+            args.push(make_nestedmeta_list("auto_cfg", nested));
+        }
+
+        if self.vis.value_sync.is_some() || self.vis.value_async.is_some() {
+            let mut nested = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
+            if let Some(vis) = &self.vis.value_sync {
+                nested.push(make_nestedmeta_namevalue("sync", vis));
+            }
+            if let Some(vis) = &self.vis.value_async {
+                nested.push(make_nestedmeta_namevalue("async", vis));
+            }
+            args.push(make_nestedmeta_list("vis", nested));
+        }
+
+        if !self.methods.is_empty() {
+            let mut nested = Punctuated::<syn::NestedMeta, syn::token::Comma>::new();
+            for (name, value) in &self.methods {
+                nested.push(make_nestedmeta_namevalue(name.as_str(), value.as_str()));
+            }
+            let arg = make_nestedmeta_list("methods", nested);
+            args.push(arg);
+        }
+
         for version in &self.versions {
             let (name, nested) = match version.kind {
                 ConvertMode::IntoSync | ConvertMode::IntoAsync => {
@@ -439,12 +943,38 @@ impl MacroParameters {
     pub fn extend_tokenstream2_with_cfg_outer_attrs(
         &self,
         ts: &mut TokenStream2,
+        convert_mode: ConvertMode,
     ) -> syn::Result<()> {
+        if let Some(cond) = self.cfg_cond(convert_mode) {
+            ts.extend(quote!(#[cfg(#cond)]));
+        }
+
+        self.extend_tokenstream2_with_outer_attrs(ts)
+    }
+
+    /// Condition of the version: `cfg`, `key_cfg` and `auto_cfg` combined by `all(...)`.
+    pub fn cfg_cond(&self, convert_mode: ConvertMode) -> Option<TokenStream2> {
+        let mut conds = vec![];
         if let Some(cfg_cond) = &self.cfg {
-            let cfg_ts = cfg_cond.into_token_stream();
-            ts.extend(quote!(#[cfg(#cfg_ts)]));
-        };
+            conds.push(cfg_cond.into_token_stream());
+        }
+        if let (Some(key), true) = (&self.key, self.key_cfg) {
+            let key = LitStr::new(key.as_str(), Span::call_site());
+            conds.push(quote!(feature = #key));
+        }
+        if let Some(feature) = self.auto_cfg_get(convert_mode) {
+            let feature = LitStr::new(feature, Span::call_site());
+            conds.push(quote!(feature = #feature));
+        }
+
+        match conds.as_slice() {
+            [] => None,
+            [cond] => Some(cond.clone()),
+            _ => Some(quote!(all(#(#conds),*))),
+        }
+    }
 
+    pub fn extend_tokenstream2_with_outer_attrs(&self, ts: &mut TokenStream2) -> syn::Result<()> {
         for attr in &self.outer_attrs {
             match attr {
                 NestedMeta::Meta(_) => {
@@ -484,6 +1014,27 @@ impl MacroParameters {
         Ok(())
     }
 
+    /// Attributes of `add_attrs` for the given mode.
+    pub fn add_attrs_get(&self, convert_mode: ConvertMode) -> syn::Result<Vec<Attribute>> {
+        let list = match convert_mode {
+            ConvertMode::IntoSync => &self.add_attrs_sync,
+            ConvertMode::IntoAsync => &self.add_attrs_async,
+        };
+
+        let mut attrs = vec![];
+        for attr in list {
+            match attr {
+                NestedMeta::Meta(meta) => attrs.push(syn::parse_quote!(#[#meta])),
+                NestedMeta::Lit(syn::Lit::Str(s)) => {
+                    attrs.push(make_attr_from_str(s.value(), attr.span())?)
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(attrs)
+    }
+
     pub fn to_tokens(&self, add_mode: Option<ConvertMode>) -> TokenStream2 {
         self.to_nestedmeta(add_mode).to_token_stream()
     }
@@ -492,79 +1043,369 @@ impl MacroParameters {
         IdentRecord::with_fn_mode( fn_mode )
     }
 
+    /// Parameters of a nested scope (`maybe` on a method of the converted item): the parent ones
+    /// are applied as for a version, and the version itself (mode, `key`, name of the item) is kept.
+    pub fn apply_scope(child: &mut MacroParameters, parent: &MacroParameters) -> syn::Result<()> {
+        Self::apply_parent(child, parent)?;
+
+        child.mode = parent.mode;
+        child.key = parent.key.clone();
+        child.self_name = parent.self_name.clone();
+        child.original_self_name = parent.original_self_name.clone();
+        child.keep_self = parent.keep_self;
+
+        Ok(())
+    }
+
     pub fn apply_parent(child: &mut MacroParameters, parent: &MacroParameters) -> syn::Result<()> {
         if parent.disable {
             child.disable = true;
         }
 
+        if parent.force {
+            child.force = true;
+        }
+
         if parent.keep_self {
             child.keep_self = true;
         }
 
-        if !parent.idents.is_empty() {
-            child.idents.extend(parent.idents.clone());
+        // `conditional_attrs`: the versions are merged into one item, which keeps its name
+        if parent.conditional_attrs {
+            child.keep_self = true;
         }
 
-        if !parent.drop_attrs.is_empty() {
-            let mut new_drop_attrs = parent.drop_attrs.clone();
-            new_drop_attrs.extend_from_slice(&child.drop_attrs);
-            child.drop_attrs = new_drop_attrs;
+        if parent.key_cfg {
+            child.key_cfg = true;
         }
 
-        if !parent.replace_features.is_empty() {
-            child
-                .replace_features
-                .extend(parent.replace_features.clone());
+        if parent.trim_cfg {
+            child.trim_cfg = true;
         }
 
-        Ok(())
-    }
+        if parent.keep_asyncness {
+            child.keep_asyncness = true;
+        }
 
-    pub fn disable_get(&self) -> bool {
-        self.disable
-    }
+        if parent.keep_runtime_attrs {
+            child.keep_runtime_attrs = true;
+        }
 
-    pub fn mode_get(&self) -> Option<ConvertMode> {
-        self.mode
-    }
+        if parent.reexport {
+            child.reexport = true;
+        }
 
-    pub fn key_get<'s>(&'s self) -> Option<&'s str> {
-        self.key.as_ref().map(|s| s.as_str())
-    }
+        if parent.rewrite_macro_body {
+            child.rewrite_macro_body = true;
+        }
 
-    pub fn original_self_name_set<S: AsRef<str>>(&mut self, name: S, fn_mode: bool) {
-        if !self.keep_self {
-            if self.idents.get(name.as_ref()).is_none() {
-                let mut ir = self.default_ident_record(fn_mode);
-    
-                if let Some(key) = &self.key {
-                    if let Some(self_name) = &self.self_name {
-                        let mut idents = HashMap::new();
-                        idents.insert( key.clone(), self_name.clone() );
-                        ir.idents = Some(idents);
-                    }
-                }
-    
-                self.idents.insert(name.as_ref().to_string(), ir);
-            }
+        if parent.strip_macro_awaits {
+            child.strip_macro_awaits = true;
         }
-    }
 
-    pub fn prefix_set(&mut self, prefix: String) {
-        self.prefix = Some(prefix);
-    }
+        if parent.rewrite_spawn {
+            child.rewrite_spawn = true;
+        }
 
-    pub fn prefix_get(&self) -> &str {
+        if parent.rewrite_sleep {
+            child.rewrite_sleep = true;
+        }
+
+        if parent.rewrite_boxed {
+            child.rewrite_boxed = true;
+        }
+
+        if parent.dump {
+            child.dump = true;
+        }
+
+        if parent.keep_unsupported {
+            child.keep_unsupported = true;
+        }
+
+        if parent.check_features {
+            child.check_features = true;
+        }
+
+        if child.rewrite_stream.is_none() {
+            child.rewrite_stream = parent.rewrite_stream.clone();
+        }
+
+        if child.sync_via_block_on.is_none() {
+            child.sync_via_block_on = parent.sync_via_block_on.clone();
+        }
+
+        // a version may override the prefix, e.g. when its output lives in another module
+        if child.prefix.is_none() {
+            child.prefix = parent.prefix.clone();
+        }
+
+        if child.macro_path.is_none() {
+            child.macro_path = parent.macro_path.clone();
+        }
+
+        if child.send.is_none() {
+            child.send = parent.send;
+        }
+
+        if child.async_trait.is_none() {
+            child.async_trait = parent.async_trait;
+        }
+
+        if child.drop_pin.is_none() {
+            child.drop_pin = parent.drop_pin;
+        }
+
+        if child.as_name.is_none() {
+            child.as_name = parent.as_name.clone();
+        }
+
+        if child.async_trait_cfg.is_none() {
+            child.async_trait_cfg = parent.async_trait_cfg.clone();
+        }
+
+        for (name, value) in &parent.idents {
+            child
+                .idents
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+
+        if !parent.drop_attrs.is_empty() {
+            let mut new_drop_attrs = parent.drop_attrs.clone();
+            new_drop_attrs.extend_from_slice(&child.drop_attrs);
+            child.drop_attrs = new_drop_attrs;
+        }
+
+        for (parent_attrs, child_attrs) in [
+            (&parent.add_attrs_sync, &mut child.add_attrs_sync),
+            (&parent.add_attrs_async, &mut child.add_attrs_async),
+        ] {
+            if !parent_attrs.is_empty() {
+                let mut new_attrs = parent_attrs.clone();
+                new_attrs.extend(child_attrs.iter().cloned());
+                *child_attrs = new_attrs;
+            }
+        }
+
+        if !parent.drop_args.is_empty() {
+            let mut new_drop_args = parent.drop_args.clone();
+            new_drop_args.extend_from_slice(&child.drop_args);
+            child.drop_args = new_drop_args;
+        }
+
+        if !parent.transparent_macros.is_empty() {
+            let mut new_macros = parent.transparent_macros.clone();
+            new_macros.extend_from_slice(&child.transparent_macros);
+            child.transparent_macros = new_macros;
+        }
+
+        if !parent.drop_bounds.is_empty() {
+            let mut new_drop_bounds = parent.drop_bounds.clone();
+            new_drop_bounds.extend_from_slice(&child.drop_bounds);
+            child.drop_bounds = new_drop_bounds;
+        }
+
+        // a replacement of the version overrides the common one
+        for (name, value) in &parent.replace_features {
+            child
+                .replace_features
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+
+        // the condition of a version is written in terms of the original features too
+        if let Some(mut cfg) = child.cfg.take() {
+            child.replace_features_in_meta(&mut cfg, &mut vec![]);
+            child.cfg = Some(cfg);
+        }
+
+        for (name, record) in &parent.replace_ident {
+            child
+                .replace_ident
+                .entry(name.clone())
+                .or_insert_with(|| record.clone());
+        }
+
+        for (name, record) in &parent.replace_path {
+            child
+                .replace_path
+                .entry(name.clone())
+                .or_insert_with(|| record.clone());
+        }
+
+        for (name, value) in &parent.rename_crate {
+            child
+                .rename_crate
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+
+        if child.auto_cfg.value_sync.is_none() {
+            child.auto_cfg.value_sync = parent.auto_cfg.value_sync.clone();
+        }
+        if child.auto_cfg.value_async.is_none() {
+            child.auto_cfg.value_async = parent.auto_cfg.value_async.clone();
+        }
+
+        if child.vis.value_sync.is_none() {
+            child.vis.value_sync = parent.vis.value_sync.clone();
+        }
+        if child.vis.value_async.is_none() {
+            child.vis.value_async = parent.vis.value_async.clone();
+        }
+
+        for (name, value) in &parent.methods {
+            child
+                .methods
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+
+        Ok(())
+    }
+
+    pub fn disable_get(&self) -> bool {
+        self.disable && !self.force
+    }
+
+    pub fn mode_get(&self) -> Option<ConvertMode> {
+        self.mode
+    }
+
+    pub fn key_get<'s>(&'s self) -> Option<&'s str> {
+        self.key.as_ref().map(|s| s.as_str())
+    }
+
+    pub fn key_cfg_get(&self) -> bool {
+        self.key_cfg
+    }
+
+    pub fn original_self_name_set<S: AsRef<str>>(&mut self, name: S, fn_mode: bool) {
+        self.original_self_name = Some(name.as_ref().to_string());
+
+        // explicit name of the converted item overrides any suffix rules
+        if let Some(as_name) = &self.as_name {
+            let mut ir = self.default_ident_record(fn_mode);
+            ir.ident_sync = Some(as_name.clone());
+            ir.ident_async = Some(as_name.clone());
+            self.idents.insert(name.as_ref().to_string(), ir);
+            return;
+        }
+
+        if !self.keep_self {
+            if self.idents.get(name.as_ref()).is_none() {
+                let mut ir = self.default_ident_record(fn_mode);
+    
+                if let Some(key) = &self.key {
+                    if let Some(self_name) = &self.self_name {
+                        let mut idents = BTreeMap::new();
+                        idents.insert( key.clone(), self_name.clone() );
+                        ir.idents = Some(idents);
+                    }
+                }
+    
+                self.idents.insert(name.as_ref().to_string(), ir);
+            }
+        }
+    }
+
+    pub fn prefix_set(&mut self, prefix: String) {
+        self.prefix = Some(prefix);
+    }
+
+    pub fn prefix_get(&self) -> &str {
         self.prefix
             .as_ref()
             .map(|s| s.as_str())
             .unwrap_or(DEFAULT_CRATE_NAME)
     }
 
+    pub fn trim_cfg_get(&self) -> bool {
+        self.trim_cfg
+    }
+
+    pub fn keep_asyncness_get(&self) -> bool {
+        self.keep_asyncness
+    }
+
+    pub fn keep_runtime_attrs_get(&self) -> bool {
+        self.keep_runtime_attrs
+    }
+
+    pub fn reexport_get(&self) -> bool {
+        self.reexport
+    }
+
+    pub fn rewrite_macro_body_get(&self) -> bool {
+        self.rewrite_macro_body
+    }
+
+    pub fn strip_macro_awaits_get(&self) -> bool {
+        self.strip_macro_awaits
+    }
+
+    pub fn rewrite_spawn_get(&self) -> bool {
+        self.rewrite_spawn
+    }
+
+    pub fn rewrite_sleep_get(&self) -> bool {
+        self.rewrite_sleep
+    }
+
+    pub fn rewrite_boxed_get(&self) -> bool {
+        self.rewrite_boxed
+    }
+
+    pub fn dump_get(&self) -> bool {
+        self.dump
+    }
+
+    pub fn keep_unsupported_get(&self) -> bool {
+        self.keep_unsupported
+    }
+
+    pub fn check_features_get(&self) -> bool {
+        self.check_features
+    }
+
+    pub fn conditional_attrs_get(&self) -> bool {
+        self.conditional_attrs
+    }
+
+    pub fn cfg_get(&self) -> Option<&Meta> {
+        self.cfg.as_ref()
+    }
+
+    pub fn async_trait_get(&self) -> AsyncTraitBackend {
+        self.async_trait.unwrap_or(AsyncTraitBackend::AsyncTrait)
+    }
+
+    /// Whether `pin-project` attributes are dropped, by default only in the sync version.
+    pub fn drop_pin_get(&self, convert_mode: ConvertMode) -> bool {
+        match (self.drop_pin, convert_mode) {
+            (Some(drop_pin), _) => drop_pin,
+            (None, ConvertMode::IntoSync) => true,
+            (None, ConvertMode::IntoAsync) => false,
+        }
+    }
+
     pub fn send_get(&self) -> Option<bool> {
         self.send
     }
 
+    pub fn rewrite_stream_get(&self) -> Option<&str> {
+        self.rewrite_stream.as_deref()
+    }
+
+    pub fn sync_via_block_on_get(&self) -> Option<&str> {
+        self.sync_via_block_on.as_deref()
+    }
+
+    pub fn async_trait_cfg_get(&self) -> Option<&Meta> {
+        self.async_trait_cfg.as_ref()
+    }
+
     pub fn idents_get<'s, S: AsRef<str>>(&'s self, name: S) -> Option<&'s IdentRecord> {
         self.idents.get(name.as_ref())
     }
@@ -572,18 +1413,139 @@ impl MacroParameters {
     pub fn replace_features_is_empty(&self) -> bool {
         self.replace_features.is_empty()
     }
-    pub fn replace_features_get<'s, S: AsRef<str>>(&'s self, name: S) -> Option<&'s str> {
-        self.replace_features.get(name.as_ref()).map(|s| s.as_str())
+    pub fn replace_features_get<S: AsRef<str>>(&self, name: S) -> Option<&FeatureReplacement> {
+        self.replace_features.get(name.as_ref())
+    }
+    /// Applies `replace_feature` to `feature = "..."` leaves of a cfg predicate, the replaced
+    /// features are added to `replaced` as `(from, to)` pairs.
+    pub fn replace_features_in_meta(&self, meta: &mut Meta, replaced: &mut Vec<(String, String)>) -> bool {
+        let mut changed = false;
+
+        match meta {
+            Meta::NameValue(MetaNameValue { path, lit: Lit::Str(s), .. }) if path.is_ident("feature") => {
+                let prev = s.value();
+                if let Some(new) = self.replace_features_get(&prev) {
+                    replaced.push((prev, new.to_string()));
+
+                    match new {
+                        FeatureReplacement::Feature(name) => *s = LitStr::new(name, s.span()),
+                        FeatureReplacement::Predicate(predicate) => *meta = predicate.clone(),
+                        // `all()` is always true
+                        FeatureReplacement::Removed => *meta = syn::parse_quote!(all()),
+                    }
+                    changed = true;
+                }
+            }
+            Meta::List(list) => {
+                for nm in &mut list.nested {
+                    if let NestedMeta::Meta(m) = nm {
+                        changed |= self.replace_features_in_meta(m, replaced);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        changed
+    }
+    /// Iterates over configured feature replacements as `(from, to)` pairs.
+    pub fn replace_features_iter(&self) -> impl Iterator<Item = (&str, &FeatureReplacement)> {
+        self.replace_features
+            .iter()
+            .map(|(from, to)| (from.as_str(), to))
+    }
+
+    pub fn original_self_name_get(&self) -> Option<&str> {
+        self.original_self_name.as_deref()
+    }
+
+    pub fn replace_ident_get<S: AsRef<str>>(&self, name: S) -> Option<&PathRecord> {
+        self.replace_ident.get(name.as_ref())
+    }
+
+    pub fn replace_path_is_empty(&self) -> bool {
+        self.replace_path.is_empty()
+    }
+    pub fn replace_path_iter(&self) -> impl Iterator<Item = (&str, &PathRecord)> {
+        self.replace_path.iter().map(|(name, record)| (name.as_str(), record))
+    }
+
+    pub fn auto_cfg_get(&self, convert_mode: ConvertMode) -> Option<&str> {
+        self.auto_cfg.get(convert_mode)
+    }
+
+    /// Visibility of `vis` for the given mode (checked when parsed).
+    pub fn vis_get(&self, convert_mode: ConvertMode) -> Option<syn::Visibility> {
+        self.vis.get(convert_mode).and_then(|vis| syn::parse_str(vis).ok())
+    }
+
+    pub fn rename_crate_get<S: AsRef<str>>(&self, name: S) -> Option<&str> {
+        self.rename_crate.get(name.as_ref()).map(|s| s.as_str())
+    }
+
+    pub fn methods_get<S: AsRef<str>>(&self, name: S) -> Option<&str> {
+        self.methods.get(name.as_ref()).map(|s| s.as_str())
     }
 
     pub fn drop_attrs_is_empty(&self) -> bool {
         self.drop_attrs.is_empty()
     }
-    pub fn drop_attrs_contains(&self, name: &String) -> bool {
-        self.drop_attrs.contains(name)
+    pub fn drop_attrs_contains(&self, path: &str) -> bool {
+        self.drop_attrs.iter().any(|matcher| matcher.matches(path))
+    }
+    /// Names of the attributes dropped from the converted code.
+    pub fn drop_attrs(&self) -> &[AttrMatcher] {
+        &self.drop_attrs
+    }
+
+    pub fn drop_args_is_empty(&self) -> bool {
+        self.drop_args.is_empty()
+    }
+    pub fn drop_args_contains(&self, name: &str) -> bool {
+        self.drop_args.iter().any(|arg| arg == name)
+    }
+
+    /// Macros whose arguments are parsed as expressions and converted: the standard ones and
+    /// those of `transparent_macros`.
+    pub fn transparent_macros_contains(&self, name: &str) -> bool {
+        STANDARD_MACROS.contains(&name) || self.transparent_macros.iter().any(|mac| mac == name)
+    }
+
+    pub fn drop_bounds_is_empty(&self) -> bool {
+        self.drop_bounds.is_empty()
+    }
+    pub fn drop_bounds_contains(&self, name: &str) -> bool {
+        self.drop_bounds.iter().any(|bound| bound == name)
+    }
+
+    /// `a::b::maybe` -> (["a", "b"], "maybe")
+    fn macro_path_split(&self) -> Option<(Vec<&str>, &str)> {
+        let mut segments = self.macro_path.as_ref()?.split("::").collect::<Vec<_>>();
+        let last = segments.pop()?;
+        Some((segments, last))
     }
 
     pub fn is_our_attr(&self, attr: &Attribute) -> Option<String> {
+        // other macros of the crate are expected next to `maybe`: `a::b::only_if`
+        if let Some((module, maybe_name)) = self.macro_path_split() {
+            let path = &attr.path;
+            if attr.style != syn::AttrStyle::Outer
+                || path.leading_colon.is_some()
+                || path.segments.len() != module.len() + 1
+                || path.segments.iter().any(|segment| !segment.arguments.is_empty())
+                || !path.segments.iter().zip(&module).all(|(segment, name)| segment.ident == name)
+            {
+                return None;
+            }
+
+            let last = path.segments.last()?.ident.to_string();
+            return if last == maybe_name {
+                Some(MACRO_MAYBE_NAME.to_string())
+            } else {
+                Some(last)
+            };
+        }
+
         if attr.style == syn::AttrStyle::Outer {
             if attr.path.leading_colon.is_none() && attr.path.segments.len() == 2 {
                 let first_segment = &attr.path.segments[0];
@@ -609,6 +1571,12 @@ impl MacroParameters {
     }
 
     pub fn make_self_path(&self, name: &str) -> syn::Path {
+        if let Some((module, maybe_name)) = self.macro_path_split() {
+            let name = if name == MACRO_MAYBE_NAME { maybe_name } else { name };
+            let path = module.into_iter().chain(Some(name)).collect::<Vec<_>>();
+            return make_path(&path.join("::"));
+        }
+
         let mut segments = Punctuated::<syn::PathSegment, syn::token::Colon2>::new();
         segments.push_value(syn::PathSegment {
             ident: Ident::new(
@@ -631,10 +1599,6 @@ impl MacroParameters {
             segments,
         }
     }
-
-    pub fn standard_macros<'s>(&'s self) -> &[&'s str] {
-        STANDARD_MACROS
-    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -650,17 +1614,52 @@ impl MacroParametersBuilder {
             params: MacroParameters {
                 mode: None,
                 disable: false,
+                force: false,
                 key: None,
-                self_name: None, 
+                self_name: None,
+                original_self_name: None,
+                as_name: None,
                 prefix: None,
-                idents: HashMap::new(),
+                macro_path: None,
+                idents: BTreeMap::new(),
                 keep_self: false,
+                key_cfg: false,
+                trim_cfg: false,
+                keep_asyncness: false,
+                keep_runtime_attrs: false,
+                reexport: false,
+                rewrite_macro_body: false,
+                strip_macro_awaits: false,
+                rewrite_spawn: false,
+                rewrite_sleep: false,
+                rewrite_boxed: false,
+                dump: false,
+                keep_unsupported: false,
+                check_features: false,
+                conditional_attrs: false,
+                strict_params: false,
                 send: None,
+                async_trait: None,
+                drop_pin: None,
+                rewrite_stream: None,
+                sync_via_block_on: None,
+                async_trait_cfg: None,
                 cfg: None,
                 outer_attrs: Punctuated::new(),
                 inner_attrs: Punctuated::new(),
+                add_attrs_sync: Punctuated::new(),
+                add_attrs_async: Punctuated::new(),
                 drop_attrs: vec![],
-                replace_features: HashMap::new(),
+                drop_args: vec![],
+                transparent_macros: vec![],
+                drop_bounds: vec![],
+                replace_features: BTreeMap::new(),
+                replace_ident: BTreeMap::new(),
+                replace_path: BTreeMap::new(),
+                rename_crate: BTreeMap::new(),
+                auto_cfg: ReplaceRecord::default(),
+                vis: ReplaceRecord::default(),
+                methods: BTreeMap::new(),
                 versions: vec![],
             },
         }
@@ -686,44 +1685,144 @@ impl MacroParametersBuilder {
         Ok(())
     }
 
+    pub fn as_name(&mut self, as_name: String) -> syn::Result<()> {
+        self.params.as_name = Some(as_name);
+        Ok(())
+    }
+
     pub fn disable(&mut self) {
         self.params.disable = true;
     }
 
+    pub fn force(&mut self) {
+        self.params.force = true;
+    }
+
     pub fn keep_self(&mut self) {
         self.params.keep_self = true;
     }
 
+    pub fn key_cfg(&mut self) {
+        self.params.key_cfg = true;
+    }
+
+    pub fn trim_cfg(&mut self) {
+        self.params.trim_cfg = true;
+    }
+
+    pub fn keep_asyncness(&mut self) {
+        self.params.keep_asyncness = true;
+    }
+
+    pub fn keep_runtime_attrs(&mut self) {
+        self.params.keep_runtime_attrs = true;
+    }
+
+    pub fn reexport(&mut self) {
+        self.params.reexport = true;
+    }
+
+    pub fn rewrite_macro_body(&mut self) {
+        self.params.rewrite_macro_body = true;
+    }
+
+    pub fn strip_macro_awaits(&mut self) {
+        self.params.strip_macro_awaits = true;
+    }
+
+    pub fn rewrite_spawn(&mut self) {
+        self.params.rewrite_spawn = true;
+    }
+
+    pub fn rewrite_sleep(&mut self) {
+        self.params.rewrite_sleep = true;
+    }
+
+    pub fn rewrite_boxed(&mut self) {
+        self.params.rewrite_boxed = true;
+    }
+
+    pub fn dump(&mut self) {
+        self.params.dump = true;
+    }
+
+    pub fn keep_unsupported(&mut self) {
+        self.params.keep_unsupported = true;
+    }
+
+    pub fn check_features(&mut self) {
+        self.params.check_features = true;
+    }
+
+    pub fn conditional_attrs(&mut self) {
+        self.params.conditional_attrs = true;
+    }
+
+    pub fn strict_params(&mut self) {
+        self.params.strict_params = true;
+    }
+
+    pub fn check_unknown_param(&self, name: &str, meta: &Meta) -> syn::Result<()> {
+        if !self.params.strict_params || KNOWN_ATTRS.contains(&name) {
+            return Ok(());
+        }
+
+        let suggestion = KNOWN_PARAMS
+            .iter()
+            .map(|known| (edit_distance(name, known), known))
+            .filter(|(distance, _)| *distance <= 2)
+            .min_by_key(|(distance, _)| *distance);
+
+        match suggestion {
+            Some((_, known)) => Err(syn::Error::new_spanned(
+                meta.to_token_stream(),
+                format!("Unknown parameter `{}`, did you mean `{}`?", name, known),
+            )),
+            None => Ok(()),
+        }
+    }
+
     pub fn prefix(&mut self, prefix: String) -> syn::Result<()> {
         self.params.prefix = Some(prefix);
         Ok(())
     }
 
+    pub fn macro_path(&mut self, macro_path: String) -> syn::Result<()> {
+        let path = syn::parse_str::<syn::Path>(&macro_path)?;
+        let segments = match path_to_string(&path) {
+            Some(segments) if path.segments.len() > 1 => segments,
+            _ => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "Expected path to the `maybe` macro, like `my_crate::macros::maybe`",
+                ))
+            }
+        };
+        self.params.macro_path = Some(segments);
+        Ok(())
+    }
+
     pub fn idents(
-        idents: &mut HashMap<String, IdentRecord>,
+        idents: &mut BTreeMap<String, IdentRecord>,
         list: &Punctuated<NestedMeta, Comma>,
     ) -> syn::Result<()> {
         for nm in list {
             match nm {
                 NestedMeta::Meta(Meta::Path(path)) => {
-                    let ident = path
-                        .get_ident()
-                        .ok_or(syn::Error::new_spanned(
-                            nm.to_token_stream(),
-                            "Expected ident, but not complex path",
-                        ))?
-                        .to_string();
+                    // `a::Client` renames only paths ending with `a::Client`
+                    let key = path_to_string(path).ok_or(syn::Error::new_spanned(
+                        nm.to_token_stream(),
+                        "Expected ident or simple path",
+                    ))?;
                     let ir = IdentRecord::new();
-                    idents.insert(ident, ir);
+                    idents.insert(key, ir);
                 }
                 NestedMeta::Meta(Meta::List(syn::MetaList { path, nested, .. })) => {
-                    let ident = path
-                        .get_ident()
-                        .ok_or(syn::Error::new_spanned(
-                            nm.to_token_stream(),
-                            "Expected ident, but not complex path",
-                        ))?
-                        .to_string();
+                    let key = path_to_string(path).ok_or(syn::Error::new_spanned(
+                        nm.to_token_stream(),
+                        "Expected ident or simple path",
+                    ))?;
+                    let ident = path.segments.last().unwrap().ident.to_string();
                     let mut ir = IdentRecord::new();
                     for inm in nested {
                         match inm {
@@ -742,6 +1841,9 @@ impl MacroParametersBuilder {
                                     "use" => {
                                         ir.use_mode = true;
                                     }
+                                    "method" => {
+                                        ir.method_mode = true;
+                                    }
                                     "keep" => {
                                         ir.keep = true;
                                     }
@@ -754,7 +1856,7 @@ impl MacroParametersBuilder {
                                     _ => {
                                         return Err(syn::Error::new_spanned(
                                             nm.to_token_stream(),
-                                            "Expected fn, use, keep, sync, async",
+                                            "Expected fn, use, method, keep, sync, async",
                                         ))
                                     }
                                 }
@@ -780,7 +1882,7 @@ impl MacroParametersBuilder {
                                         ir.ident_async = Some(ivalue);
                                     }
                                     _ => {
-                                        let idents = ir.idents.get_or_insert_with(|| HashMap::new());
+                                        let idents = ir.idents.get_or_insert_with(BTreeMap::new);
                                         idents.insert(iname, ivalue);
                                     }
                                 }
@@ -793,7 +1895,7 @@ impl MacroParametersBuilder {
                             }
                         }
                     }
-                    idents.insert(ident, ir);
+                    idents.insert(key, ir);
                 }
                 _ => {
                     return Err(syn::Error::new_spanned(
@@ -807,6 +1909,32 @@ impl MacroParametersBuilder {
         Ok(())
     }
 
+    pub fn async_trait(&mut self, async_trait: String) -> syn::Result<()> {
+        self.params.async_trait = Some(AsyncTraitBackend::from_str(&async_trait).ok_or_else(|| {
+            syn::Error::new(
+                Span::call_site(),
+                "Only accepts `async_trait`, `native` or `trait_variant`",
+            )
+        })?);
+
+        Ok(())
+    }
+
+    pub fn drop_pin(&mut self, drop_pin: String) -> syn::Result<()> {
+        self.params.drop_pin = Some(match drop_pin.as_str() {
+            "" | "true" => true,
+            "false" => false,
+            _ => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "Only accepts `true` or `false`",
+                ));
+            }
+        });
+
+        Ok(())
+    }
+
     pub fn send(&mut self, send: String) -> syn::Result<()> {
         self.params.send = Some(match send.as_str() {
             "" | "Send" | "true" => true,
@@ -822,6 +1950,34 @@ impl MacroParametersBuilder {
         Ok(())
     }
 
+    pub fn rewrite_stream(&mut self, path: String) -> syn::Result<()> {
+        let path = match path.as_str() {
+            "false" => return Ok(()),
+            "" | "true" => "Stream".to_string(),
+            _ => {
+                syn::parse_str::<syn::Path>(&path)?;
+                path
+            }
+        };
+
+        self.params.rewrite_stream = Some(path);
+        Ok(())
+    }
+
+    pub fn sync_via_block_on(&mut self, path: String) -> syn::Result<()> {
+        let path = match path.as_str() {
+            "false" => return Ok(()),
+            "" | "true" => "futures::executor::block_on".to_string(),
+            _ => {
+                syn::parse_str::<syn::Path>(&path)?;
+                path
+            }
+        };
+
+        self.params.sync_via_block_on = Some(path);
+        Ok(())
+    }
+
     pub fn feature(&mut self, meta: &Meta) -> syn::Result<()> {
         self.cfg_meta(meta)
     }
@@ -857,6 +2013,19 @@ impl MacroParametersBuilder {
         Ok(())
     }
 
+    pub fn async_trait_cfg(&mut self, list: &MetaList) -> syn::Result<()> {
+        match (list.nested.len(), list.nested.first()) {
+            (1, Some(NestedMeta::Meta(meta))) => {
+                self.params.async_trait_cfg = Some(meta.clone());
+                Ok(())
+            }
+            _ => Err(syn::Error::new_spanned(
+                list.to_token_stream(),
+                "Expected condition",
+            )),
+        }
+    }
+
     pub fn cfg_meta(&mut self, meta: &Meta) -> syn::Result<()> {
         self.params.cfg = Some(meta.clone());
         Ok(())
@@ -890,11 +2059,45 @@ impl MacroParametersBuilder {
         if let Some(kind) = ConvertMode::from_str(name) {
             self.version(kind, list)?;
         } else {
+            self.check_unknown_param(name, meta)?;
             self.params.inner_attrs.push(NestedMeta::Meta(meta.clone()));
         };
         Ok(())
     }
 
+    pub fn add_attrs(&mut self, list: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
+        for nm in list {
+            let (kind, nested) = match nm {
+                NestedMeta::Meta(Meta::List(MetaList { path, nested, .. })) => {
+                    (path.get_ident().map(|ident| ident.to_string()), nested)
+                }
+                _ => (None, &Punctuated::new()),
+            };
+            let target = match kind.as_deref() {
+                Some("sync") => &mut self.params.add_attrs_sync,
+                Some("async") => &mut self.params.add_attrs_async,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nm.to_token_stream(),
+                        "Expected `sync(...)` or `async(...)`",
+                    ))
+                }
+            };
+            for attr in nested {
+                match attr {
+                    NestedMeta::Meta(_) | NestedMeta::Lit(Lit::Str(_)) => target.push(attr.clone()),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            attr.to_token_stream(),
+                            "Expected attribute or string literal",
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn inner_attrs(&mut self, list: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
         if self.params.inner_attrs.is_empty() {
             self.params.inner_attrs = list.clone();
@@ -909,7 +2112,7 @@ impl MacroParametersBuilder {
         kind: ConvertMode,
         list: &Punctuated<NestedMeta, Comma>,
     ) -> syn::Result<()> {
-        let inner = MacroParameters::from_args(list)?;
+        let inner = MacroParameters::from_args_with_strict(list, self.params.strict_params)?;
         self.params.versions.push(MacroParameterVersion {
             kind,
             params: inner,
@@ -918,6 +2121,57 @@ impl MacroParametersBuilder {
     }
 
     pub fn drop_attrs(&mut self, meta: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
+        for nm in meta {
+            match nm {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    let name = attr_path_to_string(path);
+                    self.params.drop_attrs.push(AttrMatcher::from_str(&name));
+                }
+                NestedMeta::Lit(Lit::Str(lit)) => {
+                    let value = lit.value();
+                    let path = value.trim();
+                    let path = path.strip_suffix("::*").unwrap_or(path);
+                    syn::parse_str::<syn::Path>(path).map_err(|_| {
+                        syn::Error::new_spanned(lit, "Expected attribute path or path prefix (`tokio::*`)")
+                    })?;
+                    self.params.drop_attrs.push(AttrMatcher::from_str(&value));
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nm.to_token_stream(),
+                        "Expected list of attribute paths",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn drop_args(&mut self, meta: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
+        for nm in meta {
+            match nm {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    let name = path
+                        .get_ident()
+                        .ok_or(syn::Error::new_spanned(
+                            path.to_token_stream(),
+                            "Expected ident",
+                        ))?
+                        .to_string();
+                    self.params.drop_args.push(name);
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nm.to_token_stream(),
+                        "Expected list of idents",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn transparent_macros(&mut self, meta: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
         for nm in meta {
             match nm {
                 NestedMeta::Meta(Meta::Path(path)) => {
@@ -928,7 +2182,31 @@ impl MacroParametersBuilder {
                             "Expected ident",
                         ))?
                         .to_string();
-                    self.params.drop_attrs.push(name);
+                    self.params.transparent_macros.push(name);
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nm.to_token_stream(),
+                        "Expected list of macro names",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn drop_bounds(&mut self, meta: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
+        for nm in meta {
+            match nm {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    let name = path
+                        .get_ident()
+                        .ok_or(syn::Error::new_spanned(
+                            path.to_token_stream(),
+                            "Expected ident",
+                        ))?
+                        .to_string();
+                    self.params.drop_bounds.push(name);
                 }
                 _ => {
                     return Err(syn::Error::new_spanned(
@@ -954,11 +2232,13 @@ impl MacroParametersBuilder {
                     }
                 };
                 let new = match &meta[1] {
-                    NestedMeta::Lit(Lit::Str(lit)) => lit.value(),
+                    NestedMeta::Lit(Lit::Str(lit)) if lit.value().is_empty() => FeatureReplacement::Removed,
+                    NestedMeta::Lit(Lit::Str(lit)) => FeatureReplacement::Feature(lit.value()),
+                    NestedMeta::Meta(meta @ Meta::List(_)) => FeatureReplacement::Predicate(meta.clone()),
                     nm @ _ => {
                         return Err(syn::Error::new_spanned(
                             nm.to_token_stream(),
-                            "Expected string literal",
+                            "Expected string literal or cfg predicate",
                         ))
                     }
                 };
@@ -968,7 +2248,7 @@ impl MacroParametersBuilder {
             _ => {
                 return Err(syn::Error::new_spanned(
                     meta.to_token_stream(),
-                    "Expected two string literals",
+                    "Expected feature name and its replacement",
                 ))
             }
         };
@@ -976,6 +2256,176 @@ impl MacroParametersBuilder {
         Ok(())
     }
 
+    pub fn replace_ident(&mut self, meta: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
+        let (name, record) = Self::replace_record(meta)?;
+        let ident = syn::parse_str::<Ident>(&name)
+            .map_err(|_| syn::Error::new_spanned(meta[0].to_token_stream(), "Expected identifier"))?;
+        self.params.replace_ident.insert(ident.to_string(), record);
+        Ok(())
+    }
+
+    pub fn replace_path(&mut self, meta: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
+        let (name, record) = Self::replace_record(meta)?;
+        let path = syn::parse_str::<syn::Path>(&name)
+            .map_err(|_| syn::Error::new_spanned(meta[0].to_token_stream(), "Expected path"))?;
+        let segments = normalized_path_segments(&path)
+            .ok_or_else(|| syn::Error::new_spanned(meta[0].to_token_stream(), "Expected path without generic arguments"))?;
+        self.params.replace_path.insert(segments.join("::"), record);
+        Ok(())
+    }
+
+    /// `"Name", sync = "path", async = "path"`
+    fn replace_record(meta: &Punctuated<NestedMeta, Comma>) -> syn::Result<(String, PathRecord)> {
+        let name = match meta.first() {
+            Some(NestedMeta::Lit(Lit::Str(lit))) => lit.value(),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    meta.to_token_stream(),
+                    "Expected string literal",
+                ))
+            }
+        };
+
+        let mut record = PathRecord::default();
+        for nm in meta.iter().skip(1) {
+            match nm {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit),
+                    ..
+                })) => {
+                    let value = syn::parse_str::<syn::Path>(&lit.value())
+                        .map_err(|_| syn::Error::new_spanned(lit, "Expected path"))?;
+                    let value = Some(value);
+                    match ConvertMode::from_str(path.to_token_stream().to_string()) {
+                        Some(ConvertMode::IntoSync) => record.path_sync = value,
+                        Some(ConvertMode::IntoAsync) => record.path_async = value,
+                        None => {
+                            return Err(syn::Error::new_spanned(
+                                path.to_token_stream(),
+                                "Expected `sync` or `async`",
+                            ))
+                        }
+                    }
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nm.to_token_stream(),
+                        "Expected sync = \"path\" or async = \"path\" pair",
+                    ))
+                }
+            }
+        }
+
+        Ok((name, record))
+    }
+
+    pub fn auto_cfg(&mut self, meta: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
+        Self::mode_values(&mut self.params.auto_cfg, meta, "feature", |_| Ok(()))
+    }
+
+    pub fn vis(&mut self, meta: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
+        Self::mode_values(&mut self.params.vis, meta, "pub(crate)", |lit| {
+            syn::parse_str::<syn::Visibility>(&lit.value())
+                .map(|_| ())
+                .map_err(|_| syn::Error::new_spanned(lit, "Expected a visibility (`pub`, `pub(crate)`, ...)"))
+        })
+    }
+
+    /// Parses `sync = "value", async = "value"` pairs into `record`, checking each value.
+    fn mode_values(
+        record: &mut ReplaceRecord,
+        meta: &Punctuated<NestedMeta, Comma>,
+        example: &str,
+        check: impl Fn(&LitStr) -> syn::Result<()>,
+    ) -> syn::Result<()> {
+        for nm in meta {
+            match nm {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit),
+                    ..
+                })) => {
+                    check(lit)?;
+                    let value = Some(lit.value());
+                    match ConvertMode::from_str(path.to_token_stream().to_string()) {
+                        Some(ConvertMode::IntoSync) => record.value_sync = value,
+                        Some(ConvertMode::IntoAsync) => record.value_async = value,
+                        None => {
+                            return Err(syn::Error::new_spanned(
+                                path.to_token_stream(),
+                                "Expected `sync` or `async`",
+                            ))
+                        }
+                    }
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nm.to_token_stream(),
+                        format!("Expected sync = \"{0}\" or async = \"{0}\" pair", example),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn rename_crate(&mut self, meta: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
+        for nm in meta {
+            match nm {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit),
+                    ..
+                })) => {
+                    let name = path
+                        .get_ident()
+                        .ok_or(syn::Error::new_spanned(
+                            path.to_token_stream(),
+                            "Expected crate name",
+                        ))?
+                        .to_string();
+                    self.params.rename_crate.insert(name, lit.value());
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nm.to_token_stream(),
+                        "Expected crate = \"new_crate\" pair",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn methods(&mut self, meta: &Punctuated<NestedMeta, Comma>) -> syn::Result<()> {
+        for nm in meta {
+            match nm {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit),
+                    ..
+                })) => {
+                    let name = path
+                        .get_ident()
+                        .ok_or(syn::Error::new_spanned(
+                            path.to_token_stream(),
+                            "Expected method name",
+                        ))?
+                        .to_string();
+                    self.params.methods.insert(name, lit.value());
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nm.to_token_stream(),
+                        "Expected method = \"template\" pair",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn build(mut self) -> syn::Result<MacroParameters> {
         let mut versions = std::mem::replace(&mut self.params.versions, vec![]);
 
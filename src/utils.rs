@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use syn::{
     parenthesized,
@@ -72,10 +74,12 @@ pub(crate) use unwrap_or_set_error_and_return;
 
 pub(crate) fn make_path(name: &str) -> syn::Path {
     let mut segments = Punctuated::<syn::PathSegment, syn::token::Colon2>::new();
-    segments.push_value(syn::PathSegment {
-        ident: Ident::new(name, Span::call_site()),
-        arguments: syn::PathArguments::None,
-    });
+    for segment in name.split("::") {
+        segments.push(syn::PathSegment {
+            ident: Ident::new(segment, Span::call_site()),
+            arguments: syn::PathArguments::None,
+        });
+    }
 
     syn::Path {
         leading_colon: None,
@@ -83,6 +87,47 @@ pub(crate) fn make_path(name: &str) -> syn::Path {
     }
 }
 
+/// `a::b::C` -> "a::b::C", paths with generic arguments or a leading `::` are rejected.
+pub(crate) fn path_to_string(path: &syn::Path) -> Option<String> {
+    if path.leading_colon.is_some() {
+        return None;
+    }
+
+    let mut names = vec![];
+    for segment in &path.segments {
+        if !segment.arguments.is_empty() {
+            return None;
+        }
+        names.push(segment.ident.to_string());
+    }
+
+    Some(names.join("::"))
+}
+
+/// Attribute path as written, without the leading `::`: `#[tokio::test]` -> "tokio::test".
+pub(crate) fn attr_path_to_string(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Segment names of a path without the leading `::`, `crate::` or `self::`: `::a::B` -> ["a", "B"].
+/// Paths with generic arguments are rejected.
+pub(crate) fn normalized_path_segments(path: &syn::Path) -> Option<Vec<String>> {
+    let mut names = vec![];
+    for segment in &path.segments {
+        if !segment.arguments.is_empty() {
+            return None;
+        }
+        names.push(segment.ident.to_string());
+    }
+
+    let skip = names.iter().take_while(|name| *name == "crate" || *name == "self").count();
+    Some(names.split_off(skip))
+}
+
 pub(crate) fn make_nestedmeta_namevalue(name: &str, value: &str) -> syn::NestedMeta {
     NestedMeta::Meta(Meta::NameValue(syn::MetaNameValue {
         path: make_path(name),
@@ -119,6 +164,88 @@ pub(crate) fn make_attr_ts_from_str<S: AsRef<str>>(s: S, span: Span) -> syn::Res
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+
+    prev[b.len()]
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Collects `"name"` literals of all `feature = "name"` in a cfg condition.
+pub(crate) fn cfg_features(meta: &Meta, features: &mut Vec<syn::LitStr>) {
+    match meta {
+        Meta::NameValue(nv) if nv.path.is_ident("feature") => {
+            if let syn::Lit::Str(lit) = &nv.lit {
+                features.push(lit.clone());
+            }
+        }
+        Meta::List(list) => {
+            for nested in &list.nested {
+                if let NestedMeta::Meta(meta) = nested {
+                    cfg_features(meta, features);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Features declared in `Cargo.toml` of the crate being compiled: keys of the `[features]` table
+/// and optional dependencies (implicit features, unless referred to as `dep:name`).
+pub(crate) fn manifest_features() -> Option<HashSet<String>> {
+    let dir = std::env::var("CARGO_MANIFEST_DIR").ok()?;
+    let manifest = std::fs::read_to_string(std::path::Path::new(&dir).join("Cargo.toml")).ok()?;
+    let manifest: toml::Value = manifest.parse().ok()?;
+
+    let mut features = HashSet::new();
+    let mut dep_refs = HashSet::new();
+    if let Some(table) = manifest.get("features").and_then(toml::Value::as_table) {
+        for (name, enables) in table {
+            features.insert(name.clone());
+            for value in enables.as_array().into_iter().flatten().filter_map(toml::Value::as_str) {
+                if let Some(dep) = value.strip_prefix("dep:") {
+                    dep_refs.insert(dep.to_string());
+                }
+            }
+        }
+    }
+
+    let targets = manifest
+        .get("target")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values());
+    for table in std::iter::once(&manifest).chain(targets) {
+        for kind in ["dependencies", "build-dependencies"] {
+            let deps = match table.get(kind).and_then(toml::Value::as_table) {
+                Some(deps) => deps,
+                None => continue,
+            };
+            for (name, dep) in deps {
+                let optional = dep.get("optional").and_then(toml::Value::as_bool).unwrap_or(false);
+                if optional && !dep_refs.contains(name) {
+                    features.insert(name.clone());
+                }
+            }
+        }
+    }
+
+    Some(features)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
 struct VecOfAttrs {
     pub attrs: Vec<syn::Attribute>,
 }
@@ -164,6 +291,137 @@ impl Parse for PunctuatedList {
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// Arguments of `matches!(expr, pattern if guard)`. The pattern is kept as tokens.
+pub struct MatchesArgs {
+    pub expr: Expr,
+    comma: Comma,
+    pattern: TokenStream2,
+    pub guard: Option<(Token![if], Expr)>,
+    trailing: Option<Comma>,
+}
+
+impl Parse for MatchesArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let expr = input.parse()?;
+        let comma = input.parse()?;
+
+        // the pattern never contains `if` at the top level, a trailing comma stays with it
+        let mut pattern = TokenStream2::new();
+        while !input.is_empty() && !input.peek(Token![if]) {
+            let tt: proc_macro2::TokenTree = input.parse()?;
+            pattern.extend(Some(tt));
+        }
+
+        let guard = if input.peek(Token![if]) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+
+        Ok(MatchesArgs {
+            expr,
+            comma,
+            pattern,
+            guard,
+            trailing: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for MatchesArgs {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.expr.to_tokens(tokens);
+        self.comma.to_tokens(tokens);
+        self.pattern.to_tokens(tokens);
+        if let Some((if_token, guard)) = &self.guard {
+            if_token.to_tokens(tokens);
+            guard.to_tokens(tokens);
+        }
+        self.trailing.to_tokens(tokens);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Body of `define!`: template name followed by `maybe` parameters.
+pub struct TemplateDefinition {
+    pub name: Ident,
+    _comma: Option<Comma>,
+    pub args: Punctuated<NestedMeta, Comma>,
+}
+
+impl Parse for TemplateDefinition {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(TemplateDefinition {
+            name: input.parse()?,
+            _comma: input.parse()?,
+            args: Punctuated::<NestedMeta, Comma>::parse_terminated(input)?,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Arguments of `body!`: `sync = { ... }, async = { ... }`.
+pub struct BodyVariants {
+    pub sync: Option<syn::Block>,
+    pub async_: Option<syn::Block>,
+}
+
+impl Parse for BodyVariants {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut variants = BodyVariants { sync: None, async_: None };
+
+        while !input.is_empty() {
+            let name = input.call(<Ident as syn::ext::IdentExt>::parse_any)?;
+            input.parse::<Token![=]>()?;
+            let block: syn::Block = input.parse()?;
+
+            let slot = match name.to_string().as_str() {
+                "sync" => &mut variants.sync,
+                "async" => &mut variants.async_,
+                _ => return Err(syn::Error::new(name.span(), "Expected `sync` or `async`")),
+            };
+            if slot.is_some() {
+                return Err(syn::Error::new(name.span(), "Duplicated body"));
+            }
+            *slot = Some(block);
+
+            if !input.is_empty() {
+                input.parse::<Comma>()?;
+            }
+        }
+
+        Ok(variants)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Inline const block (`const { ... }`), syn parses it as `Expr::Verbatim`.
+pub struct InlineConst {
+    pub const_token: Token![const],
+    pub block: syn::Block,
+}
+
+impl Parse for InlineConst {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(InlineConst {
+            const_token: input.parse()?,
+            block: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for InlineConst {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.const_token.to_tokens(tokens);
+        self.block.to_tokens(tokens);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
 pub struct DebugByDisplay<T: std::fmt::Display>(pub T);
 
 impl<T: std::fmt::Display> std::fmt::Debug for DebugByDisplay<T> {
@@ -17,6 +17,7 @@ use crate::utils::unwrap_or_error;
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum IdentMode {
     Use,
+    Method { on_self: bool },
     Other,
 }
 
@@ -31,6 +32,9 @@ pub trait VisitMutExt {
     fn process_attribute(&mut self, _node: &mut syn::Attribute) -> syn::Result<()> {
         Ok(())
     }
+    fn process_const_expr(&mut self, _node: &mut syn::Expr) -> syn::Result<()> {
+        Ok(())
+    }
     fn process_expr(&mut self, _node: &mut syn::Expr) -> syn::Result<()> {
         Ok(())
     }
@@ -43,19 +47,55 @@ pub trait VisitMutExt {
     fn process_macro(&mut self, _node: &mut syn::Macro) -> syn::Result<()> {
         Ok(())
     }
+    fn process_path(&mut self, _node: &mut syn::Path) -> syn::Result<()> {
+        Ok(())
+    }
     fn process_path_segment(&mut self, _node: &mut syn::PathSegment) -> syn::Result<()> {
         Ok(())
     }
+    fn process_return_type(&mut self, _node: &mut syn::ReturnType) -> syn::Result<()> {
+        Ok(())
+    }
+    fn process_signature(&mut self, _node: &mut syn::Signature) -> syn::Result<()> {
+        Ok(())
+    }
+    fn process_stmt(&mut self, _node: &mut syn::Stmt) -> syn::Result<()> {
+        Ok(())
+    }
+    fn process_trait_item_type(&mut self, _node: &mut syn::TraitItemType) -> syn::Result<()> {
+        Ok(())
+    }
+    fn process_type(&mut self, _node: &mut syn::Type) -> syn::Result<()> {
+        Ok(())
+    }
     fn process_type_param(&mut self, _node: &mut syn::TypeParam) -> syn::Result<()> {
         Ok(())
     }
     fn process_use_tree(&mut self, _node: &mut syn::UseTree) -> syn::Result<()> {
         Ok(())
     }
+    fn process_generics_scope(&mut self, _node: &mut syn::Generics) -> syn::Result<()> {
+        Ok(())
+    }
+    fn process_fn_generics_scope(&mut self, node: &mut syn::Generics) -> syn::Result<()> {
+        self.process_generics_scope(node)
+    }
+    fn process_params_scope(&mut self, _attrs: &mut Vec<syn::Attribute>) -> syn::Result<()> {
+        Ok(())
+    }
 
     fn after_process_item(&mut self, _node: &mut syn::Item) -> syn::Result<()> {
         Ok(())
     }
+    fn after_process_generics_scope(&mut self, _node: &mut syn::Generics) -> syn::Result<()> {
+        Ok(())
+    }
+    fn after_process_field_value(&mut self, _node: &mut syn::FieldValue) -> syn::Result<()> {
+        Ok(())
+    }
+    fn after_process_params_scope(&mut self, _attrs: &mut Vec<syn::Attribute>) -> syn::Result<()> {
+        Ok(())
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -93,8 +133,11 @@ macro_rules! impl_fn {
     (@expr $value:expr, (_, value)) => {
         &mut $value.1
     };
-    (@func $self:expr, $node:ident, $proc:ident(node $(.$path:ident)? as Some($expr:tt) $(, $mode:expr)?) ) => {
-        if let Some(value) = impl_fn!(@arg $node $(.$path)?) {
+    (@expr $value:expr, (_, value, _)) => {
+        &mut $value.1
+    };
+    (@func $self:expr, $node:ident, $proc:ident(node $(.$path:ident)* as Some($expr:tt) $(, $mode:expr)?) ) => {
+        if let Some(value) = impl_fn!(@arg $node $(.$path)*) {
             match $self.$proc( impl_fn!(@expr value, $expr) $(, $mode)? ) {
                 Ok(_) => {},
                 Err(e) => {
@@ -103,8 +146,8 @@ macro_rules! impl_fn {
             };
         };
     };
-    (@func $self:expr, $node:ident, $proc:ident(node $(.$path:ident)? $(, $mode:expr)?) ) => {
-        match $self.$proc( impl_fn!(@arg $node $(.$path)?) $(, $mode)? ) {
+    (@func $self:expr, $node:ident, $proc:ident(node $(.$path:ident)* $(, $mode:expr)?) ) => {
+        match $self.$proc( impl_fn!(@arg $node $(.$path)*) $(, $mode)? ) {
             Ok(_) => {},
             Err(e) => {
                 emit_error!(e)
@@ -191,14 +234,25 @@ impl<T> VisitMut for Visitor<T> where Self: VisitMutExt,
     impl_fn!(visit_expr_loop_mut,           syn::ExprLoop,          { process_attrs(node.attrs); });
     impl_fn!(visit_expr_macro_mut,          syn::ExprMacro,         { process_attrs(node.attrs); });
     impl_fn!(visit_expr_match_mut,          syn::ExprMatch,         { process_attrs(node.attrs); });
-    impl_fn!(visit_expr_method_call_mut,    syn::ExprMethodCall,    { process_attrs(node.attrs); });
+
+    fn visit_expr_method_call_mut(&mut self, node: &mut syn::ExprMethodCall) {
+        impl_fn!(@funcs self, node, { process_attrs(node.attrs); });
+
+        let on_self = matches!(&*node.receiver, syn::Expr::Path(p) if p.path.is_ident("self"));
+        if let Err(e) = self.process_ident(&mut node.method, IdentMode::Method { on_self }) {
+            emit_error!(e)
+        };
+
+        visit_mut::visit_expr_method_call_mut(self, node);
+    }
+
     impl_fn!(visit_expr_paren_mut,          syn::ExprParen,         { process_attrs(node.attrs); });
-    impl_fn!(visit_expr_path_mut,           syn::ExprPath,          { process_attrs(node.attrs); });
+    impl_fn!(visit_expr_path_mut,           syn::ExprPath,          { process_attrs(node.attrs); process_path(node.path); });
     impl_fn!(visit_expr_range_mut,          syn::ExprRange,         { process_attrs(node.attrs); });
     impl_fn!(visit_expr_reference_mut,      syn::ExprReference,     { process_attrs(node.attrs); });
-    impl_fn!(visit_expr_repeat_mut,         syn::ExprRepeat,        { process_attrs(node.attrs); });
+    impl_fn!(visit_expr_repeat_mut,         syn::ExprRepeat,        { process_attrs(node.attrs); process_const_expr(node.len); });
     impl_fn!(visit_expr_return_mut,         syn::ExprReturn,        { process_attrs(node.attrs); });
-    impl_fn!(visit_expr_struct_mut,         syn::ExprStruct,        { process_attrs(node.attrs); });
+    impl_fn!(visit_expr_struct_mut,         syn::ExprStruct,        { process_attrs(node.attrs); process_path(node.path); });
     impl_fn!(visit_expr_try_mut,            syn::ExprTry,           { process_attrs(node.attrs); });
     impl_fn!(visit_expr_try_block_mut,      syn::ExprTryBlock,      { process_attrs(node.attrs); });
     impl_fn!(visit_expr_tuple_mut,          syn::ExprTuple,         { process_attrs(node.attrs); });
@@ -209,7 +263,7 @@ impl<T> VisitMut for Visitor<T> where Self: VisitMutExt,
     impl_fn!(visit_expr_yield_mut,          syn::ExprYield,         { process_attrs(node.attrs); });
     impl_fn!(visit_field_mut,               syn::Field,             { process_attrs(node.attrs); });
     impl_fn!(visit_field_pat_mut,           syn::FieldPat,          { process_attrs(node.attrs); });
-    impl_fn!(visit_field_value_mut,         syn::FieldValue,        { process_attrs(node.attrs); });
+    impl_fn!(visit_field_value_mut,         syn::FieldValue,        { process_attrs(node.attrs); }, { after_process_field_value(node); });
     impl_fn!(visit_fields_mut,              syn::Fields,            );
     impl_fn!(visit_fields_named_mut,        syn::FieldsNamed,       );
     impl_fn!(visit_fields_unnamed_mut,      syn::FieldsUnnamed,     );
@@ -228,27 +282,27 @@ impl<T> VisitMut for Visitor<T> where Self: VisitMutExt,
     impl_fn!(visit_generics_mut,            syn::Generics,          );
     impl_fn!(visit_ident_mut,               syn::Ident,             );
     impl_fn!(visit_impl_item_mut,           syn::ImplItem,          );
-    impl_fn!(visit_impl_item_const_mut,     syn::ImplItemConst,     { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
+    impl_fn!(visit_impl_item_const_mut,     syn::ImplItemConst,     { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); process_const_expr(node.expr); });
     impl_fn!(visit_impl_item_macro_mut,     syn::ImplItemMacro,     { process_attrs(node.attrs); });
-    impl_fn!(visit_impl_item_method_mut,    syn::ImplItemMethod,    { process_attrs(node.attrs); });
-    impl_fn!(visit_impl_item_type_mut,      syn::ImplItemType,      { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
+    impl_fn!(visit_impl_item_method_mut,    syn::ImplItemMethod,    { process_params_scope(node.attrs); process_attrs(node.attrs); process_fn_generics_scope(node.sig.generics); }, { after_process_generics_scope(node.sig.generics); after_process_params_scope(node.attrs); });
+    impl_fn!(visit_impl_item_type_mut,      syn::ImplItemType,      { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); process_generics_scope(node.generics); }, { after_process_generics_scope(node.generics); });
     impl_fn!(visit_index_mut,               syn::Index,             );
     impl_fn!(visit_item_mut,                syn::Item,              { process_item(node); },            { after_process_item(node); });
-    impl_fn!(visit_item_const_mut,          syn::ItemConst,         { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
-    impl_fn!(visit_item_enum_mut,           syn::ItemEnum,          { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
+    impl_fn!(visit_item_const_mut,          syn::ItemConst,         { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); process_const_expr(node.expr); });
+    impl_fn!(visit_item_enum_mut,           syn::ItemEnum,          { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); process_generics_scope(node.generics); }, { after_process_generics_scope(node.generics); });
     impl_fn!(visit_item_extern_crate_mut,   syn::ItemExternCrate,   { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); process_ident(node.rename as Some((_, value)), IdentMode::Other); });
-    impl_fn!(visit_item_fn_mut,             syn::ItemFn,            { process_attrs(node.attrs); });
+    impl_fn!(visit_item_fn_mut,             syn::ItemFn,            { process_attrs(node.attrs); process_fn_generics_scope(node.sig.generics); }, { after_process_generics_scope(node.sig.generics); });
     impl_fn!(visit_item_foreign_mod_mut,    syn::ItemForeignMod,    { process_attrs(node.attrs); });
-    impl_fn!(visit_item_impl_mut,           syn::ItemImpl,          { process_attrs(node.attrs); });
+    impl_fn!(visit_item_impl_mut,           syn::ItemImpl,          { process_attrs(node.attrs); process_generics_scope(node.generics); process_path(node.trait_ as Some((_, value, _))); }, { after_process_generics_scope(node.generics); });
     impl_fn!(visit_item_macro_mut,          syn::ItemMacro,         { process_attrs(node.attrs); process_ident(node.ident as Some(value), IdentMode::Other); });
     impl_fn!(visit_item_macro2_mut,         syn::ItemMacro2,        { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
     impl_fn!(visit_item_mod_mut,            syn::ItemMod,           { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
     impl_fn!(visit_item_static_mut,         syn::ItemStatic,        { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
-    impl_fn!(visit_item_struct_mut,         syn::ItemStruct,        { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
-    impl_fn!(visit_item_trait_mut,          syn::ItemTrait,         { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
+    impl_fn!(visit_item_struct_mut,         syn::ItemStruct,        { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); process_generics_scope(node.generics); }, { after_process_generics_scope(node.generics); });
+    impl_fn!(visit_item_trait_mut,          syn::ItemTrait,         { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); process_generics_scope(node.generics); }, { after_process_generics_scope(node.generics); });
     impl_fn!(visit_item_trait_alias_mut,    syn::ItemTraitAlias,    { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
-    impl_fn!(visit_item_type_mut,           syn::ItemType,          { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
-    impl_fn!(visit_item_union_mut,          syn::ItemUnion,         { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
+    impl_fn!(visit_item_type_mut,           syn::ItemType,          { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); process_generics_scope(node.generics); }, { after_process_generics_scope(node.generics); });
+    impl_fn!(visit_item_union_mut,          syn::ItemUnion,         { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); process_generics_scope(node.generics); }, { after_process_generics_scope(node.generics); });
     impl_fn!(visit_item_use_mut,            syn::ItemUse,           { process_attrs(node.attrs); });
     impl_fn!(visit_label_mut,               syn::Label,             );
     impl_fn!(visit_lifetime_mut,            syn::Lifetime,          );
@@ -298,19 +352,19 @@ impl<T> VisitMut for Visitor<T> where Self: VisitMutExt,
     impl_fn!(visit_qself_mut,               syn::QSelf,             );
     impl_fn!(visit_range_limits_mut,        syn::RangeLimits,       );
     impl_fn!(visit_receiver_mut,            syn::Receiver,          { process_attrs(node.attrs); });
-    impl_fn!(visit_return_type_mut,         syn::ReturnType,        );
-    impl_fn!(visit_signature_mut,           syn::Signature,         { process_ident(node.ident, IdentMode::Other); } );
+    impl_fn!(visit_return_type_mut,         syn::ReturnType,        { process_return_type(node); });
+    impl_fn!(visit_signature_mut,           syn::Signature,         { process_signature(node); process_ident(node.ident, IdentMode::Other); } );
     impl_fn!(visit_span_mut,                Span,                   );
-    impl_fn!(visit_stmt_mut,                syn::Stmt,              );
-    impl_fn!(visit_trait_bound_mut,         syn::TraitBound,        );
+    impl_fn!(visit_stmt_mut,                syn::Stmt,              { process_stmt(node); });
+    impl_fn!(visit_trait_bound_mut,         syn::TraitBound,        { process_path(node.path); });
     impl_fn!(visit_trait_bound_modifier_mut,syn::TraitBoundModifier,);
     impl_fn!(visit_trait_item_mut,          syn::TraitItem,         );
-    impl_fn!(visit_trait_item_const_mut,    syn::TraitItemConst,    { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
+    impl_fn!(visit_trait_item_const_mut,    syn::TraitItemConst,    { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); process_const_expr(node.default as Some((_, value))); });
     impl_fn!(visit_trait_item_macro_mut,    syn::TraitItemMacro,    { process_attrs(node.attrs); });
-    impl_fn!(visit_trait_item_method_mut,   syn::TraitItemMethod,   { process_attrs(node.attrs); });
-    impl_fn!(visit_trait_item_type_mut,     syn::TraitItemType,     { process_attrs(node.attrs); process_ident(node.ident, IdentMode::Other); });
-    impl_fn!(visit_type_mut,                syn::Type,              );
-    impl_fn!(visit_type_array_mut,          syn::TypeArray,         );
+    impl_fn!(visit_trait_item_method_mut,   syn::TraitItemMethod,   { process_params_scope(node.attrs); process_attrs(node.attrs); process_fn_generics_scope(node.sig.generics); }, { after_process_generics_scope(node.sig.generics); after_process_params_scope(node.attrs); });
+    impl_fn!(visit_trait_item_type_mut,     syn::TraitItemType,     { process_attrs(node.attrs); process_trait_item_type(node); process_ident(node.ident, IdentMode::Other); process_generics_scope(node.generics); }, { after_process_generics_scope(node.generics); });
+    impl_fn!(visit_type_mut,                syn::Type,              { process_type(node); });
+    impl_fn!(visit_type_array_mut,          syn::TypeArray,         { process_const_expr(node.len); });
     impl_fn!(visit_type_bare_fn_mut,        syn::TypeBareFn,        );
     impl_fn!(visit_type_group_mut,          syn::TypeGroup,         );
     impl_fn!(visit_type_impl_trait_mut,     syn::TypeImplTrait,     );
@@ -320,7 +374,7 @@ impl<T> VisitMut for Visitor<T> where Self: VisitMutExt,
     impl_fn!(visit_type_param_mut,          syn::TypeParam,         { process_attrs(node.attrs); process_type_param(node); });
     impl_fn!(visit_type_param_bound_mut,    syn::TypeParamBound,    );
     impl_fn!(visit_type_paren_mut,          syn::TypeParen,         );
-    impl_fn!(visit_type_path_mut,           syn::TypePath,          );
+    impl_fn!(visit_type_path_mut,           syn::TypePath,          { process_path(node.path); });
     impl_fn!(visit_type_ptr_mut,            syn::TypePtr,           );
     impl_fn!(visit_type_reference_mut,      syn::TypeReference,     );
     impl_fn!(visit_type_slice_mut,          syn::TypeSlice,         );
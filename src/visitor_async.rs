@@ -1,34 +1,76 @@
 #[allow(unused_imports)]
-use std::{collections::HashMap, iter::FromIterator};
+use std::{collections::{HashMap, HashSet}, iter::FromIterator};
 
 #[allow(unused_imports)]
 use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, Group, Spacing, TokenStream as TokenStream2, TokenTree};
 use quote::{quote, ToTokens};
-use syn::visit_mut::VisitMut;
+use syn::{parse::Parser, visit_mut::VisitMut};
 
 use crate::{
-    MACRO_NOOP_NAME, MACRO_REMOVE_NAME, MACRO_ONLY_IF_NAME, MACRO_REMOVE_IF_NAME,
+    debug::ConversionReport,
+    MACRO_BODY_NAME, MACRO_KEEP_NAME, MACRO_MAYBE_NAME, MACRO_NOOP_NAME, MACRO_REMOVE_NAME, MACRO_ONLY_IF_NAME, MACRO_REMOVE_IF_NAME,
     params::{ConvertMode, MacroParameters},
-    utils::{AttributeArgsInParens, PunctuatedList},
+    utils::{attr_path_to_string, AttributeArgsInParens, BodyVariants, InlineConst, MatchesArgs, PunctuatedList},
     visit_ext::{IdentMode, VisitMutExt, Visitor},
 };
 
 pub struct AsyncAwaitVisitor<'p> {
     convert_mode: ConvertMode,
     params: &'p mut MacroParameters,
-    generics: Vec<HashMap<String, syn::PathSegment>>,
+    generics: Vec<HashMap<String, syn::Type>>,
+    shadowed: Vec<HashSet<String>>,
+    /// `send = "?Send"`: the code is not `Send`, rewrites requiring it are not applied
+    not_send: bool,
+    /// the next async block is a boxed future kept without `rewrite_boxed`, it is not flattened
+    keep_async_block: bool,
+    /// parameters of the enclosing scopes, replaced by a `maybe` attribute on a method
+    params_scopes: Vec<Option<MacroParameters>>,
+    report: Option<ConversionReport>,
 }
 
 impl<'p> AsyncAwaitVisitor<'p> {
     pub fn new(params: &'p mut MacroParameters, convert_mode: ConvertMode) -> Self {
+        let not_send = params.send_get() == Some(false);
+        let dump = params.dump_get();
         Self {
             convert_mode,
             params,
             generics: vec![],
+            shadowed: vec![],
+            not_send,
+            keep_async_block: false,
+            params_scopes: vec![],
+            report: ConversionReport::from_env().or_else(|| dump.then(ConversionReport::default)),
         }
     }
 
-    fn generics_get<S: AsRef<str>>(&self, key: S) -> Option<&syn::PathSegment> {
+    /// Writes the conversion report, if enabled by `MAYBE_ASYNC_CFG_REPORT`, and returns it (it is
+    /// also collected for `dump`).
+    pub fn write_report<S: AsRef<str>>(&mut self, item: S) -> Option<ConversionReport> {
+        let report = self.report.take()?;
+        report.write(item.as_ref(), self.convert_mode.to_str(), self.params.key_get());
+        Some(report)
+    }
+
+    fn report_rename(&mut self, from: &syn::Ident, to: &syn::Ident) {
+        if let Some(report) = &mut self.report {
+            if from != to {
+                report.renames.push((from.to_string(), to.to_string()));
+            }
+        }
+    }
+
+    /// `rewrite_stream`, which is not applied with `send = "?Send"` like the other rewrites.
+    fn rewrite_stream(&self) -> Option<&str> {
+        if self.not_send {
+            None
+        } else {
+            self.params.rewrite_stream_get()
+        }
+    }
+
+    fn generics_get<S: AsRef<str>>(&self, key: S) -> Option<&syn::Type> {
         for gens in &self.generics {
             if let Some(ps) = gens.get(key.as_ref()) {
                 return Some(ps);
@@ -37,9 +79,146 @@ impl<'p> AsyncAwaitVisitor<'p> {
 
         None
     }
+
+    fn is_shadowed<S: AsRef<str>>(&self, name: S) -> bool {
+        self.shadowed.iter().any(|names| names.contains(name.as_ref()))
+    }
+
+    /// Converts the transcribers of `macro_rules!` rules (`(...) => { transcriber };`), the
+    /// matchers are kept as is.
+    pub fn process_macro_rules(&mut self, tokens: TokenStream2) -> TokenStream2 {
+        let mut result = vec![];
+        let mut arrow = false;
+        for tt in tokens {
+            match tt {
+                TokenTree::Group(group) if arrow => {
+                    let mut converted = Group::new(group.delimiter(), self.process_macro_tokens(group.stream()));
+                    converted.set_span(group.span());
+                    result.push(TokenTree::Group(converted));
+                }
+                tt => result.push(tt),
+            }
+            // `=>` is `=` (joint) followed by `>`
+            arrow = matches!(
+                &result[..],
+                [.., TokenTree::Punct(eq), TokenTree::Punct(gt)]
+                    if eq.as_char() == '=' && eq.spacing() == Spacing::Joint && gt.as_char() == '>'
+            );
+        }
+
+        result.into_iter().collect()
+    }
+
+    /// Token-level conversion, the tokens can't be parsed as Rust code because of `$var`s:
+    /// identifiers listed in `idents` are renamed, `.await` is removed in the sync version.
+    fn process_macro_tokens(&mut self, tokens: TokenStream2) -> TokenStream2 {
+        let mut result = vec![];
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(tt) = tokens.next() {
+            match tt {
+                TokenTree::Group(group) => {
+                    let mut converted = Group::new(group.delimiter(), self.process_macro_tokens(group.stream()));
+                    converted.set_span(group.span());
+                    result.push(TokenTree::Group(converted));
+                }
+                TokenTree::Punct(punct) if punct.as_char() == '.' && matches!(self.convert_mode, ConvertMode::IntoSync) => {
+                    if matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident == "await") {
+                        tokens.next();
+                        if let Some(report) = &mut self.report {
+                            report.awaits_stripped += 1;
+                        }
+                    } else {
+                        result.push(TokenTree::Punct(punct));
+                    }
+                }
+                // `$name` is a metavariable
+                TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                    result.push(TokenTree::Punct(punct));
+                    if let Some(TokenTree::Ident(_)) = tokens.peek() {
+                        result.extend(tokens.next());
+                    }
+                }
+                TokenTree::Ident(ident) => match self.params.idents_get(ident.to_string()) {
+                    Some(ir) => {
+                        let renamed = ir.ident_add_suffix(&ident, self.convert_mode, self.params.key_get());
+                        self.report_rename(&ident, &renamed);
+                        result.push(TokenTree::Ident(renamed));
+                    }
+                    None => result.push(TokenTree::Ident(ident)),
+                },
+                tt => result.push(tt),
+            }
+        }
+
+        result.into_iter().collect()
+    }
+
+    /// `.await` removal in the sync version for bodies of macros which are not parsed as Rust code,
+    /// string literals are single tokens and stay unchanged.
+    fn strip_macro_awaits(&mut self, tokens: TokenStream2) -> TokenStream2 {
+        let mut result = vec![];
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(tt) = tokens.next() {
+            match tt {
+                TokenTree::Group(group) => {
+                    let mut converted = Group::new(group.delimiter(), self.strip_macro_awaits(group.stream()));
+                    converted.set_span(group.span());
+                    result.push(TokenTree::Group(converted));
+                }
+                TokenTree::Punct(punct)
+                    if punct.as_char() == '.'
+                        && matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident == "await") =>
+                {
+                    tokens.next();
+                    if let Some(report) = &mut self.report {
+                        report.awaits_stripped += 1;
+                    }
+                }
+                tt => result.push(tt),
+            }
+        }
+
+        result.into_iter().collect()
+    }
+
+    /// Replaces the leading segments of the path matching a `replace_path` entry, the generic
+    /// arguments of the last matched segment and the rest of the path are kept.
+    fn replace_path(&mut self, node: &mut syn::Path) -> syn::Result<()> {
+        // `crate::` and `self::` are not compared (a leading `::` is not a segment)
+        let skip = node
+            .segments
+            .iter()
+            .take_while(|segment| segment.ident == "crate" || segment.ident == "self")
+            .count();
+        let names = node.segments.iter().skip(skip).map(|s| s.ident.to_string()).collect::<Vec<_>>();
+
+        let found = self
+            .params
+            .replace_path_iter()
+            .map(|(name, record)| (name.split("::").collect::<Vec<_>>(), record))
+            .filter(|(segments, _)| segments.len() <= names.len() && names.iter().zip(segments).all(|(a, b)| a == b))
+            .max_by_key(|(segments, _)| segments.len());
+        let (len, mut path) = match found {
+            Some((segments, record)) => match record.get(self.convert_mode) {
+                Some(replacement) => (segments.len(), replacement.clone()),
+                None => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+
+        let matched = &node.segments[skip + len - 1];
+        if let Some(last) = path.segments.last_mut() {
+            last.ident.set_span(matched.ident.span());
+            last.arguments = matched.arguments.clone();
+        }
+        path.segments.extend(node.segments.iter().skip(skip + len).cloned());
+        *node = path;
+
+        Ok(())
+    }
 }
 
-fn search_future_trait_bound(bound: &syn::TypeParamBound) -> Option<syn::PathSegment> {
+fn search_future_trait_bound(bound: &syn::TypeParamBound) -> Option<syn::Type> {
     if let syn::TypeParamBound::Trait(trait_bound) = bound {
         let segment = &trait_bound.path.segments[trait_bound.path.segments.len() - 1];
         let name = segment.ident.to_string();
@@ -47,9 +226,11 @@ fn search_future_trait_bound(bound: &syn::TypeParamBound) -> Option<syn::PathSeg
             // match Future<Output=Type>
             if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
                 // binding: Output=Type
-                if let syn::GenericArgument::Binding(binding) = &args.args[0] {
-                    if let syn::Type::Path(p) = &binding.ty {
-                        return Some(p.path.segments[0].clone());
+                for arg in &args.args {
+                    if let syn::GenericArgument::Binding(binding) = arg {
+                        if binding.ident == "Output" {
+                            return Some(binding.ty.clone());
+                        }
                     }
                 }
             }
@@ -59,34 +240,482 @@ fn search_future_trait_bound(bound: &syn::TypeParamBound) -> Option<syn::PathSeg
     None
 }
 
-impl<'p> AsyncAwaitVisitor<'p> {
-    fn process_replace_features_meta(&self, meta: &mut syn::Meta) -> syn::Result<bool> {
-        let mut changed = false;
+/// Returns `T` for the boxed future types `Pin<Box<dyn Future<Output = T>>>`, `BoxFuture<'a, T>`
+/// and `LocalBoxFuture<'a, T>`.
+fn search_boxed_future_output(ty: &syn::Type) -> Option<syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last()?,
+        _ => return None,
+    };
+    let args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => &args.args,
+        _ => return None,
+    };
+    let last_type = || {
+        args.iter().rev().find_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+    };
 
-        match meta {
-            syn::Meta::NameValue(syn::MetaNameValue {
-                path,
-                lit: syn::Lit::Str(s),
-                ..
-            }) => {
-                if let Some(ident) = path.get_ident() {
-                    if ident.to_string() == "feature" {
-                        let prev = s.value();
-                        if let Some(new) = self.params.replace_features_get(&prev) {
-                            *s = syn::LitStr::new(new, s.span());
-                            changed = true;
+    if segment.ident == "BoxFuture" || segment.ident == "LocalBoxFuture" {
+        return last_type().cloned();
+    }
+
+    if segment.ident == "Pin" {
+        // Box<dyn Future<Output = T> + Send + 'a>
+        if let Some(syn::Type::Path(syn::TypePath { qself: None, path })) = last_type() {
+            let segment = path.segments.last()?;
+            if segment.ident != "Box" {
+                return None;
+            }
+            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                if let Some(syn::GenericArgument::Type(syn::Type::TraitObject(object))) = args.args.first() {
+                    return object.bounds.iter().find_map(search_future_trait_bound);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds generic parameters bounded by `Future<Output = T>` (`F: Fn() -> Fut, Fut: Future<Output = T>`),
+/// removes them with their where predicates and returns the map of their `Output` types.
+fn strip_future_generics(generics: &mut syn::Generics) -> HashMap<String, syn::Type> {
+    let mut gens: HashMap<String, syn::Type> = HashMap::new();
+
+    // generic params: <T:Future<Output=()>, F>
+    for param in &generics.params {
+        // generic param: T:Future<Output=()>
+        if let syn::GenericParam::Type(type_param) = param {
+            // bound: Future<Output=()>
+            for bound in &type_param.bounds {
+                if let Some(ty) = search_future_trait_bound(bound) {
+                    gens.insert(type_param.ident.to_string(), ty);
+                }
+            }
+        }
+    }
+
+    if let Some(where_clause) = &generics.where_clause {
+        for predicate in &where_clause.predicates {
+            if let syn::WherePredicate::Type(predicate_type) = predicate {
+                if let Some(ident) = bounded_generic_ident(&predicate_type.bounded_ty) {
+                    for bound in &predicate_type.bounds {
+                        if let Some(ty) = search_future_trait_bound(bound) {
+                            gens.insert(ident.to_string(), ty);
                         }
                     }
                 }
             }
-            syn::Meta::List(list) => {
-                for nm in &mut list.nested {
-                    if let syn::NestedMeta::Meta(m) = nm {
-                        changed |= self.process_replace_features_meta(m)?;
+        }
+    }
+
+    if gens.is_empty() {
+        return gens;
+    }
+
+    // remove generic type from generics <T, F>
+    let params = std::mem::take(&mut generics.params);
+    generics.params = params
+        .into_iter()
+        .filter(|param| match param {
+            syn::GenericParam::Type(type_param) => {
+                !gens.contains_key(&type_param.ident.to_string())
+            }
+            _ => true,
+        })
+        .collect();
+
+    // remove generic type from where clause
+    if let Some(where_clause) = &mut generics.where_clause {
+        let predicates = std::mem::take(&mut where_clause.predicates);
+        where_clause.predicates = predicates
+            .into_iter()
+            .filter(|predicate| match predicate {
+                syn::WherePredicate::Type(predicate_type) => {
+                    match bounded_generic_ident(&predicate_type.bounded_ty) {
+                        Some(ident) => !gens.contains_key(&ident.to_string()),
+                        None => true,
+                    }
+                }
+                _ => true,
+            })
+            .collect();
+    }
+
+    gens
+}
+
+/// Whether the expression may change its meaning when placed into another one without parens.
+fn needs_parens(expr: &syn::Expr) -> bool {
+    !matches!(
+        expr,
+        syn::Expr::Array(_)
+            | syn::Expr::Await(_)
+            | syn::Expr::Block(_)
+            | syn::Expr::Call(_)
+            | syn::Expr::Field(_)
+            | syn::Expr::Group(_)
+            | syn::Expr::Index(_)
+            | syn::Expr::Lit(_)
+            | syn::Expr::Macro(_)
+            | syn::Expr::MethodCall(_)
+            | syn::Expr::Paren(_)
+            | syn::Expr::Path(_)
+            | syn::Expr::Struct(_)
+            | syn::Expr::Try(_)
+            | syn::Expr::Tuple(_)
+    )
+}
+
+/// Finds the first `.await` in an expression.
+#[derive(Default)]
+struct AwaitSearch {
+    found: Option<syn::ExprAwait>,
+}
+
+impl VisitMut for AwaitSearch {
+    fn visit_expr_await_mut(&mut self, node: &mut syn::ExprAwait) {
+        if self.found.is_none() {
+            self.found = Some(node.clone());
+        }
+    }
+}
+
+/// Rejects `.await` in expressions evaluated at compile time: array lengths, const generic
+/// arguments and `const` items.
+fn check_const_context(expr: &syn::Expr) -> syn::Result<()> {
+    let mut search = AwaitSearch::default();
+    search.visit_expr_mut(&mut expr.clone());
+
+    match search.found {
+        Some(found) => Err(syn::Error::new_spanned(
+            found,
+            "`.await` cannot be used in a const context (array length, const generic argument or \
+             `const` item), it is evaluated at compile time and can't be async",
+        )),
+        None => Ok(()),
+    }
+}
+
+fn is_async_trait_lifetime(lifetime: &syn::Lifetime) -> bool {
+    lifetime.ident == "async_trait"
+}
+
+/// Removes the bounds matching `drop`, returns `true` if there are no bounds left.
+fn strip_bounds<P: Default, F: Fn(&syn::TypeParamBound) -> bool>(
+    bounds: &mut syn::punctuated::Punctuated<syn::TypeParamBound, P>,
+    drop: F,
+) -> bool {
+    let old = std::mem::take(bounds);
+    *bounds = old.into_iter().filter(|bound| !drop(bound)).collect();
+
+    bounds.is_empty()
+}
+
+fn is_async_trait_bound(bound: &syn::TypeParamBound) -> bool {
+    matches!(bound, syn::TypeParamBound::Lifetime(lt) if is_async_trait_lifetime(lt))
+}
+
+/// Removes the bounds matching `drop` from generic parameters and where clause predicates:
+/// `<T: Send>`, `where Self: Send`. Predicates without bounds left are removed.
+fn strip_generics_bounds<F: Fn(&syn::TypeParamBound) -> bool>(generics: &mut syn::Generics, drop: F) {
+    for param in &mut generics.params {
+        if let syn::GenericParam::Type(type_param) = param {
+            if strip_bounds(&mut type_param.bounds, &drop) {
+                type_param.colon_token = None;
+            }
+        }
+    }
+
+    if let Some(where_clause) = &mut generics.where_clause {
+        let predicates = std::mem::take(&mut where_clause.predicates);
+        where_clause.predicates = predicates
+            .into_iter()
+            .filter_map(|mut predicate| match &mut predicate {
+                syn::WherePredicate::Type(pred) => {
+                    if strip_bounds(&mut pred.bounds, &drop) {
+                        None
+                    } else {
+                        Some(predicate)
+                    }
+                }
+                _ => Some(predicate),
+            })
+            .collect();
+    }
+}
+
+/// Removes the `'async_trait` lifetime (left by `async_trait` expansions) from the generics:
+/// `<'async_trait>`, `'a: 'async_trait`, `where Self: 'async_trait`.
+fn strip_async_trait_generics(generics: &mut syn::Generics) {
+    let params = std::mem::take(&mut generics.params);
+    generics.params = params
+        .into_iter()
+        .filter_map(|mut param| match &mut param {
+            syn::GenericParam::Lifetime(def) if is_async_trait_lifetime(&def.lifetime) => None,
+            syn::GenericParam::Lifetime(def) => {
+                let bounds = std::mem::take(&mut def.bounds);
+                def.bounds = bounds.into_iter().filter(|lt| !is_async_trait_lifetime(lt)).collect();
+                if def.bounds.is_empty() {
+                    def.colon_token = None;
+                }
+                Some(param)
+            }
+            _ => Some(param),
+        })
+        .collect();
+
+    if let Some(where_clause) = &mut generics.where_clause {
+        let predicates = std::mem::take(&mut where_clause.predicates);
+        where_clause.predicates = predicates
+            .into_iter()
+            .filter_map(|mut predicate| match &mut predicate {
+                syn::WherePredicate::Lifetime(pred) if is_async_trait_lifetime(&pred.lifetime) => None,
+                syn::WherePredicate::Lifetime(pred) => {
+                    let bounds = std::mem::take(&mut pred.bounds);
+                    pred.bounds = bounds.into_iter().filter(|lt| !is_async_trait_lifetime(lt)).collect();
+                    if pred.bounds.is_empty() {
+                        None
+                    } else {
+                        Some(predicate)
+                    }
+                }
+                _ => Some(predicate),
+            })
+            .collect();
+    }
+
+    strip_generics_bounds(generics, is_async_trait_bound);
+}
+
+fn bounded_generic_ident(ty: &syn::Type) -> Option<&syn::Ident> {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.get_ident(),
+        _ => None,
+    }
+}
+
+fn is_future_trait_bound(bound: &syn::TypeParamBound) -> bool {
+    if let syn::TypeParamBound::Trait(trait_bound) = bound {
+        if let Some(segment) = trait_bound.path.segments.last() {
+            return segment.ident == "Future";
+        }
+    }
+
+    false
+}
+
+fn search_box_pin_arg(expr: &syn::Expr) -> Option<syn::Expr> {
+    if let syn::Expr::Call(call) = expr {
+        if let syn::Expr::Path(syn::ExprPath { path, qself: None, .. }) = &*call.func {
+            let len = path.segments.len();
+            // match Box::pin(fut) and alloc::boxed::Box::pin(fut)
+            if len >= 2
+                && path.segments[len - 2].ident == "Box"
+                && path.segments[len - 1].ident == "pin"
+                && call.args.len() == 1
+            {
+                return Some(call.args[0].clone());
+            }
+        }
+    }
+
+    None
+}
+
+/// `#[cfg(all())]`
+fn is_always_true_cfg(attr: &syn::Attribute) -> bool {
+    if !attr.path.is_ident("cfg") {
+        return false;
+    }
+
+    match attr.parse_meta() {
+        Ok(syn::Meta::List(list)) => matches!(
+            list.nested.iter().collect::<Vec<_>>().as_slice(),
+            [syn::NestedMeta::Meta(syn::Meta::List(all))] if all.path.is_ident("all") && all.nested.is_empty()
+        ),
+        _ => false,
+    }
+}
+
+/// `spawn(fut)`, `tokio::spawn(fut)`, `tokio::task::spawn(fut)` and so on -> `fut`
+fn search_spawn_arg(expr: &syn::Expr) -> Option<syn::Expr> {
+    if let syn::Expr::Call(call) = expr {
+        if let syn::Expr::Path(syn::ExprPath { path, qself: None, .. }) = &*call.func {
+            let is_spawn = path
+                .segments
+                .last()
+                .is_some_and(|last| last.ident == "spawn" && last.arguments.is_empty());
+            if is_spawn && call.args.len() == 1 {
+                return Some(call.args[0].clone());
+            }
+        }
+    }
+
+    None
+}
+
+/// `tokio::time::sleep(d)` and `async_std::task::sleep(d)` -> `std::thread::sleep(d)`,
+/// `tokio::time::sleep_until(t)` -> `std::thread::sleep(t.into_std() - std::time::Instant::now())`
+/// (saturating at zero).
+fn rewrite_sleep_call(expr: &syn::Expr) -> Option<syn::Expr> {
+    let call = match expr {
+        syn::Expr::Call(call) if call.args.len() == 1 => call,
+        _ => return None,
+    };
+    let path = match &*call.func {
+        syn::Expr::Path(syn::ExprPath { path, qself: None, .. }) => path,
+        _ => return None,
+    };
+    let names = path.segments.iter().map(|s| s.ident.to_string()).collect::<Vec<_>>();
+    let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+    let arg = &call.args[0];
+
+    match names.as_slice() {
+        [.., "tokio", "time", "sleep"] | [.., "async_std", "task", "sleep"] => {
+            Some(syn::parse_quote!(::std::thread::sleep(#arg)))
+        }
+        [.., "tokio", "time", "sleep_until"] => Some(syn::parse_quote!(
+            ::std::thread::sleep((#arg).into_std().saturating_duration_since(::std::time::Instant::now()))
+        )),
+        _ => None,
+    }
+}
+
+fn replace_impl_trait_bound(ty: &mut syn::Type, from: &str, to: &syn::Path) -> bool {
+    let mut changed = false;
+
+    if let syn::Type::ImplTrait(impl_trait) = ty {
+        for bound in &mut impl_trait.bounds {
+            if let syn::TypeParamBound::Trait(trait_bound) = bound {
+                if let Some(last) = trait_bound.path.segments.last() {
+                    if last.ident == from {
+                        // keep generic arguments: Iterator<Item = T> -> Stream<Item = T>
+                        let arguments = last.arguments.clone();
+                        let mut path = to.clone();
+                        if let Some(new_last) = path.segments.last_mut() {
+                            new_last.arguments = arguments;
+                        }
+                        trait_bound.path = path;
+                        changed = true;
                     }
                 }
             }
-            _ => {}
+        }
+    }
+
+    changed
+}
+
+/// `impl TryStream<Ok = T, Error = E>` -> `impl Iterator<Item = Result<T, E>>`
+fn replace_try_stream_bound(ty: &mut syn::Type) {
+    if let syn::Type::ImplTrait(impl_trait) = ty {
+        for bound in &mut impl_trait.bounds {
+            if let syn::TypeParamBound::Trait(trait_bound) = bound {
+                let last = match trait_bound.path.segments.last() {
+                    Some(last) if last.ident == "TryStream" => last,
+                    _ => continue,
+                };
+                let (mut ok, mut error) = (None, None);
+                if let syn::PathArguments::AngleBracketed(args) = &last.arguments {
+                    for arg in &args.args {
+                        if let syn::GenericArgument::Binding(binding) = arg {
+                            if binding.ident == "Ok" {
+                                ok = Some(binding.ty.clone());
+                            } else if binding.ident == "Error" {
+                                error = Some(binding.ty.clone());
+                            }
+                        }
+                    }
+                }
+                if let (Some(ok), Some(error)) = (ok, error) {
+                    trait_bound.path = syn::parse_quote!(Iterator<Item = Result<#ok, #error>>);
+                }
+            }
+        }
+    }
+}
+
+/// Expands a `methods` template: `$self` is replaced with the receiver and `$args` with the
+/// arguments of the method call.
+fn expand_method_template(
+    template: &str,
+    call: &syn::ExprMethodCall,
+) -> syn::Result<syn::Expr> {
+    let receiver = call.receiver.to_token_stream().to_string();
+    let args = call.args.to_token_stream().to_string();
+
+    let code = template.replace("$self", &receiver).replace("$args", &args);
+
+    syn::parse_str::<syn::Expr>(&code).map_err(|err| {
+        syn::Error::new_spanned(
+            call.to_token_stream(),
+            format!("Wrong method template `{}`: {}", template, err),
+        )
+    })
+}
+
+/// Simplifies a cfg predicate without changing its meaning: nested `all`/`any` of the same kind are
+/// flattened, identical conditions are de-duplicated, `all()` inside `all` and `any()` inside `any`
+/// are removed, and `all(x)`/`any(x)` become `x`. Empty `all()` (true) and `any()` (false) are kept.
+fn trim_cfg_predicate(nm: syn::NestedMeta) -> syn::NestedMeta {
+    let mut list = match nm {
+        syn::NestedMeta::Meta(syn::Meta::List(list)) => list,
+        nm => return nm,
+    };
+
+    let op = match list.path.get_ident() {
+        Some(ident) => ident.to_string(),
+        None => return syn::NestedMeta::Meta(syn::Meta::List(list)),
+    };
+
+    let nested = std::mem::take(&mut list.nested);
+    match op.as_str() {
+        "all" | "any" => {
+            let mut conds: Vec<syn::NestedMeta> = vec![];
+            let mut seen: HashSet<String> = HashSet::new();
+
+            for child in nested.into_iter().map(trim_cfg_predicate) {
+                let flattened = match child {
+                    syn::NestedMeta::Meta(syn::Meta::List(child)) if child.path.is_ident(&op) => {
+                        child.nested.into_iter().collect::<Vec<_>>()
+                    }
+                    child => vec![child],
+                };
+
+                for cond in flattened {
+                    if seen.insert(cond.to_token_stream().to_string()) {
+                        conds.push(cond);
+                    }
+                }
+            }
+
+            if conds.len() == 1 {
+                return conds.remove(0);
+            }
+
+            list.nested = conds.into_iter().collect();
+        }
+        "not" => {
+            list.nested = nested.into_iter().map(trim_cfg_predicate).collect();
+        }
+        _ => {
+            list.nested = nested;
+        }
+    }
+
+    syn::NestedMeta::Meta(syn::Meta::List(list))
+}
+
+impl<'p> AsyncAwaitVisitor<'p> {
+    fn process_replace_features_meta(&mut self, meta: &mut syn::Meta) -> syn::Result<bool> {
+        let mut replaced = vec![];
+        let changed = self.params.replace_features_in_meta(meta, &mut replaced);
+        if let Some(report) = &mut self.report {
+            report.features_replaced.extend(replaced);
         }
 
         Ok(changed)
@@ -149,6 +778,48 @@ impl<'p> AsyncAwaitVisitor<'p> {
         Ok(())
     }
 
+    /// `#[maybe_async_cfg::maybe(idents(..))]` on a method of the converted item: its parameters
+    /// are applied over the ones of the item until the end of the method. The attribute can't
+    /// define versions.
+    fn process_params_scope(&mut self, attrs: &mut Vec<syn::Attribute>) -> syn::Result<()> {
+        let pos = attrs
+            .iter()
+            .position(|attr| self.params.is_our_attr(attr).as_deref() == Some(MACRO_MAYBE_NAME));
+        let attr = match pos {
+            Some(pos) => attrs.remove(pos),
+            None => {
+                self.params_scopes.push(None);
+                return Ok(());
+            }
+        };
+
+        let params = MacroParameters::from_tokens_in_parens(attr.tokens.clone().into()).and_then(|mut params| {
+            if !params.versions.is_empty() {
+                return Err(syn::Error::new_spanned(&attr, "Nested `maybe` can't define versions"));
+            }
+            MacroParameters::apply_scope(&mut params, self.params)?;
+            Ok(params)
+        });
+        match params {
+            Ok(params) => {
+                self.params_scopes.push(Some(std::mem::replace(self.params, params)));
+                Ok(())
+            }
+            Err(err) => {
+                self.params_scopes.push(None);
+                Err(err)
+            }
+        }
+    }
+
+    fn after_process_params_scope(&mut self, _attrs: &mut Vec<syn::Attribute>) -> syn::Result<()> {
+        if let Some(Some(params)) = self.params_scopes.pop() {
+            *self.params = params;
+        }
+
+        Ok(())
+    }
+
     fn process_attrs(&mut self, attrs: &mut Vec<syn::Attribute>) -> syn::Result<()> {
         for attr in attrs.iter_mut() {
             if let Some(name) = self.params.is_our_attr(attr) {
@@ -164,18 +835,20 @@ impl<'p> AsyncAwaitVisitor<'p> {
         }
 
         if !self.params.drop_attrs_is_empty() {
+            let params = &self.params;
+            let report = &mut self.report;
             attrs.retain(|attr| {
-                if let Some(ident) = attr.path.get_ident() {
-                    let ident = ident.to_string();
-                    !self.params.drop_attrs_contains(&ident)
-                } else {
-                    true
+                let path = attr_path_to_string(&attr.path);
+                let drop = params.drop_attrs_contains(&path);
+                if let (true, Some(report)) = (drop, report.as_mut()) {
+                    report.attrs_dropped.push(path);
                 }
+                !drop
             });
         }
 
         if !self.params.replace_features_is_empty() {
-            for attr in attrs {
+            for attr in attrs.iter_mut() {
                 if let Some(ident) = attr.path.get_ident() {
                     if ident.to_string() == "cfg" {
                         if let Ok(mut meta) = attr.parse_meta() {
@@ -188,20 +861,136 @@ impl<'p> AsyncAwaitVisitor<'p> {
                     }
                 }
             }
+
+            // `#[cfg(feature = "x")]` with a removed feature: the attribute is dropped
+            attrs.retain(|attr| !is_always_true_cfg(attr));
+        }
+
+        if self.params.trim_cfg_get() {
+            for attr in attrs.iter_mut() {
+                if attr.path.is_ident("cfg") {
+                    if let Ok(syn::Meta::List(syn::MetaList { nested, .. })) = attr.parse_meta() {
+                        let nested = nested
+                            .into_iter()
+                            .map(trim_cfg_predicate)
+                            .collect::<syn::punctuated::Punctuated<_, syn::token::Comma>>();
+                        attr.tokens = quote!((#nested));
+                    }
+                }
+            }
         }
 
         Ok(())
     }
 
     fn process_expr(&mut self, node: &mut syn::Expr) -> syn::Result<()> {
+        // invisible groups (`$e:expr` from macro_rules) lose their precedence once re-emitted,
+        // so `$e * 2` with `$e = a().await + 1` must become `(a() + 1) * 2`
+        if let syn::Expr::Group(group) = node {
+            if needs_parens(&group.expr) {
+                *node = syn::Expr::Paren(syn::ExprParen {
+                    attrs: std::mem::take(&mut group.attrs),
+                    paren_token: syn::token::Paren(group.group_token.span),
+                    expr: group.expr.clone(),
+                });
+            }
+        }
+
+        if let syn::Expr::Macro(expr) = node {
+            if let Some(body) = self.search_body_macro(&expr.mac)? {
+                *node = body;
+            } else if let Some(kept) = self.search_keep_macro(&expr.mac) {
+                *node = kept;
+                return Ok(());
+            }
+        }
+
+        // `x.connect_async()` -> `Self::connect(x)`
+        if let syn::Expr::MethodCall(call) = node {
+            if let Some(template) = self.params.methods_get(call.method.to_string()) {
+                *node = expand_method_template(template, call)?;
+            }
+        }
+
         match self.convert_mode {
             ConvertMode::IntoSync => {
                 // async -> sync, remove async_impl blocks
                 match node {
                     syn::Expr::Await(expr) => {
-                        *node = (*expr.base).clone()
+                        if let Some(report) = &mut self.report {
+                            report.awaits_stripped += 1;
+                        }
+
+                        // `s.try_next().await` -> `s.next().transpose()`
+                        if self.rewrite_stream().is_some() {
+                            if let syn::Expr::MethodCall(call) = &*expr.base {
+                                if call.method == "try_next" && call.args.is_empty() && call.turbofish.is_none() {
+                                    let receiver = &call.receiver;
+                                    *node = syn::parse_quote!(#receiver.next().transpose());
+                                    return self.process_expr(node);
+                                }
+                            }
+                        }
+
+                        // `Box::pin(fut).await` (e.g. for async recursion) is just `fut.await`
+                        *node = search_box_pin_arg(&expr.base).unwrap_or_else(|| (*expr.base).clone());
+                        // the base may be an async block or another await
+                        return self.process_expr(node);
+                    }
+
+                    syn::Expr::Call(_) => {
+                        // `Box::pin(async move {...})` -> `{...}`, `Box::pin(fut)` -> `fut`
+                        if self.params.rewrite_boxed_get() {
+                            if let Some(arg) = search_box_pin_arg(node) {
+                                *node = arg;
+                                return self.process_expr(node);
+                            }
+                        } else if let Some(syn::Expr::Async(_)) = search_box_pin_arg(node) {
+                            // the boxed future is kept, only the awaits inside it are removed
+                            self.keep_async_block = true;
+                        }
+
+                        // `tokio::spawn(async move {...})` -> `std::thread::spawn(move || {...})`,
+                        // a thread requires `Send`
+                        if self.params.rewrite_spawn_get() && !self.not_send {
+                            if let Some(syn::Expr::Async(expr)) = search_spawn_arg(node) {
+                                let capture = expr.capture;
+                                let block = expr.block;
+                                *node = syn::parse_quote!(::std::thread::spawn(#capture || #block));
+                            }
+                        }
+
+                        if self.params.rewrite_sleep_get() {
+                            if let Some(sleep) = rewrite_sleep_call(node) {
+                                *node = sleep;
+                            }
+                        }
+                    }
+
+                    // `async move |x| {...}` -> `move |x| {...}`, the body is visited next
+                    syn::Expr::Closure(closure) => {
+                        closure.asyncness = None;
+                        // `|| async {..}` becomes `|| {..}` only with `rewrite_boxed`, otherwise
+                        // the closure still returns a future
+                        if !self.params.rewrite_boxed_get() && matches!(&*closure.body, syn::Expr::Async(_)) {
+                            self.keep_async_block = true;
+                        }
+                    }
+
+                    // `fut.boxed()`, `fut.boxed_local()` -> `fut`
+                    syn::Expr::MethodCall(call)
+                        if self.params.rewrite_boxed_get()
+                            && (call.method == "boxed" || call.method == "boxed_local")
+                            && call.args.is_empty()
+                            && call.turbofish.is_none() =>
+                    {
+                        *node = (*call.receiver).clone();
+                        // the receiver may be an async block
+                        return self.process_expr(node);
                     }
 
+                    syn::Expr::Async(_) if std::mem::take(&mut self.keep_async_block) => {}
+
                     syn::Expr::Async(expr) => {
                         let inner = &expr.block;
                         let sync_expr = if inner.stmts.len() == 1 {
@@ -217,6 +1006,8 @@ impl<'p> AsyncAwaitVisitor<'p> {
                             })
                         };
                         *node = sync_expr;
+                        // the single statement may itself be an await: `async { f().await }`
+                        return self.process_expr(node);
                     }
 
                     _ => {}
@@ -233,126 +1024,249 @@ impl<'p> AsyncAwaitVisitor<'p> {
         Ok(())
     }
 
-    fn process_item(&mut self, node: &mut syn::Item) -> syn::Result<()> {
-        match self.convert_mode {
-            ConvertMode::IntoSync => {
-                // find generic parameter of Future and replace it with its Output type
-                if let syn::Item::Fn(item_fn) = node {
-                    let mut gens: HashMap<String, syn::PathSegment> = HashMap::new();
-
-                    // generic params: <T:Future<Output=()>, F>
-                    for param in &item_fn.sig.generics.params {
-                        // generic param: T:Future<Output=()>
-                        if let syn::GenericParam::Type(type_param) = param {
-                            let generic_type_name = &type_param.ident;
-
-                            // bound: Future<Output=()>
-                            for bound in &type_param.bounds {
-                                if let Some(ps) = search_future_trait_bound(bound) {
-                                    gens.insert(generic_type_name.to_string(), ps);
-                                }
-                            }
-                        }
+    /// Returns the block of the current mode for `maybe_async_cfg::body!(sync = {...}, async = {...})`.
+    fn search_body_macro(&self, mac: &syn::Macro) -> syn::Result<Option<syn::Expr>> {
+        let path = &mac.path;
+        let is_body = path.leading_colon.is_none()
+            && path.segments.len() == 2
+            && path.segments[0].ident == self.params.prefix_get()
+            && path.segments[1].ident == MACRO_BODY_NAME;
+        if !is_body {
+            return Ok(None);
+        }
+
+        let variants = syn::parse2::<BodyVariants>(mac.tokens.clone())?;
+        let (block, name) = match self.convert_mode {
+            ConvertMode::IntoSync => (variants.sync, "sync"),
+            ConvertMode::IntoAsync => (variants.async_, "async"),
+        };
+        let block = block.ok_or_else(|| {
+            syn::Error::new_spanned(mac, format!("Body for the `{}` version is missing", name))
+        })?;
+
+        Ok(Some(syn::Expr::Block(syn::ExprBlock { attrs: vec![], label: None, block })))
+    }
+
+    /// `maybe_async_cfg::keep!(expr)` -> `expr`, left unconverted (a verbatim expression is not
+    /// visited).
+    fn search_keep_macro(&self, mac: &syn::Macro) -> Option<syn::Expr> {
+        let path = &mac.path;
+        let is_keep = path.leading_colon.is_none()
+            && path.segments.len() == 2
+            && path.segments[0].ident == self.params.prefix_get()
+            && path.segments[1].ident == MACRO_KEEP_NAME;
+        if !is_keep {
+            return None;
+        }
+
+        // an invisible group keeps the precedence of the expression
+        let group = Group::new(Delimiter::None, mac.tokens.clone());
+        Some(syn::Expr::Verbatim(TokenTree::Group(group).into()))
+    }
+
+    fn process_stmt(&mut self, node: &mut syn::Stmt) -> syn::Result<()> {
+        if let syn::Stmt::Item(syn::Item::Macro(item)) = node {
+            if let Some(expr) = self.search_keep_macro(&item.mac) {
+                *node = match item.semi_token {
+                    Some(semi) => syn::Stmt::Semi(expr, semi),
+                    None => syn::Stmt::Expr(expr),
+                };
+            } else if let Some(expr) = self.search_body_macro(&item.mac)? {
+                *node = match item.semi_token {
+                    Some(semi) => syn::Stmt::Semi(expr, semi),
+                    None => syn::Stmt::Expr(expr),
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_signature(&mut self, node: &mut syn::Signature) -> syn::Result<()> {
+        if let ConvertMode::IntoSync = self.convert_mode {
+            // &'async_trait self -> &self
+            if let Some(syn::FnArg::Receiver(receiver)) = node.inputs.first_mut() {
+                if let Some((_, lifetime)) = &mut receiver.reference {
+                    if matches!(lifetime, Some(lt) if is_async_trait_lifetime(lt)) {
+                        *lifetime = None;
                     }
+                }
+            }
+        }
 
-                    if let Some(where_clause) = &item_fn.sig.generics.where_clause {
-                        for predicate in &where_clause.predicates {
-                            if let syn::WherePredicate::Type(predicate_type) = predicate {
-                                let generic_type_name =
-                                    if let syn::Type::Path(p) = &predicate_type.bounded_ty {
-                                        &p.path.segments[0].ident
-                                    } else {
-                                        panic!("Please submit an issue");
-                                    };
-
-                                for bound in &predicate_type.bounds {
-                                    if let Some(ps) = search_future_trait_bound(bound) {
-                                        gens.insert(generic_type_name.to_string(), ps);
-                                    }
-                                }
-                            }
+        if !self.params.drop_args_is_empty() {
+            let inputs = std::mem::take(&mut node.inputs);
+            node.inputs = inputs
+                .into_iter()
+                .filter(|arg| match arg {
+                    syn::FnArg::Typed(syn::PatType { pat, .. }) => match &**pat {
+                        syn::Pat::Ident(pat_ident) => {
+                            !self.params.drop_args_contains(&pat_ident.ident.to_string())
                         }
-                    }
+                        _ => true,
+                    },
+                    syn::FnArg::Receiver(_) => true,
+                })
+                .collect();
+        }
 
-                    self.generics.push(gens);
+        Ok(())
+    }
+
+    fn process_return_type(&mut self, node: &mut syn::ReturnType) -> syn::Result<()> {
+        if let syn::ReturnType::Type(_, ty) = node {
+            match self.convert_mode {
+                ConvertMode::IntoSync => {
+                    // BoxFuture<'_, T> -> T
+                    if self.params.rewrite_boxed_get() {
+                        if let Some(output) = search_boxed_future_output(ty) {
+                            **ty = output;
+                        }
+                    }
+                    // impl Stream<Item = T> -> impl Iterator<Item = T>
+                    if self.rewrite_stream().is_some() {
+                        replace_impl_trait_bound(ty, "Stream", &syn::parse_quote!(Iterator));
+                        replace_try_stream_bound(ty);
+                    }
+                    // impl Future<Output = T> + '_ -> T
+                    if let syn::Type::ImplTrait(impl_trait) = &**ty {
+                        if let Some(output) = impl_trait.bounds.iter().find_map(search_future_trait_bound) {
+                            **ty = output;
+                        }
+                    }
                 }
+                ConvertMode::IntoAsync => {
+                    // sync-first code: impl Iterator<Item = T> -> impl Stream<Item = T>
+                    if let Some(stream) = self.rewrite_stream() {
+                        let stream = syn::parse_str::<syn::Path>(stream)?;
+                        replace_impl_trait_bound(ty, "Iterator", &stream);
+                    }
+                }
+            }
+        }
 
-                if let syn::Item::Fn(item_fn) = node {
-                    // remove generic type from generics <T, F>
-                    let args = item_fn
-                        .sig
-                        .generics
-                        .params
-                        .iter()
-                        .filter_map(|param| {
-                            if let syn::GenericParam::Type(type_param) = &param {
-                                if let Some(_) = self.generics_get(type_param.ident.to_string()) {
-                                    return None;
-                                }
-                            };
-                            Some(param)
-                        })
-                        .collect::<Vec<_>>();
-
-                    item_fn.sig.generics.params = syn::punctuated::Punctuated::from_iter(
-                        args.into_iter().map(|p| p.clone()).collect::<Vec<_>>(),
-                    );
-
-                    // remove generic type from where clause
-                    if let Some(where_clause) = &mut item_fn.sig.generics.where_clause {
-                        let new_where_clause = where_clause
-                            .predicates
-                            .iter()
-                            .filter_map(|predicate| {
-                                if let syn::WherePredicate::Type(predicate_type) = predicate {
-                                    if let syn::Type::Path(p) = &predicate_type.bounded_ty {
-                                        if let Some(_) =
-                                            self.generics_get(p.path.segments[0].ident.to_string())
-                                        {
-                                            return None;
-                                        }
-                                    }
-                                };
-                                Some(predicate)
-                            })
-                            .collect::<Vec<_>>();
+        Ok(())
+    }
+
+    fn process_trait_item_type(&mut self, node: &mut syn::TraitItemType) -> syn::Result<()> {
+        match self.convert_mode {
+            ConvertMode::IntoSync => {
+                // type Fut<'a>: Future<Output = T> + 'a; -> type Fut<'a>: 'a;
+                // generics (including GAT lifetimes) are kept as is
+                let bounds = std::mem::take(&mut node.bounds);
+                node.bounds = bounds
+                    .into_iter()
+                    .filter(|bound| !is_future_trait_bound(bound))
+                    .collect();
 
-                        where_clause.predicates = syn::punctuated::Punctuated::from_iter(
-                            new_where_clause
-                                .into_iter()
-                                .map(|c| c.clone())
-                                .collect::<Vec<_>>(),
-                        );
-                    };
+                if node.bounds.is_empty() {
+                    node.colon_token = None;
                 }
             }
             ConvertMode::IntoAsync => {}
-        };
+        }
 
         Ok(())
     }
 
-    fn after_process_item(&mut self, node: &mut syn::Item) -> syn::Result<()> {
-        match self.convert_mode {
-            ConvertMode::IntoSync => {
-                // find generic parameter of Future and replace it with its Output type
-                if let syn::Item::Fn(_item_fn) = node {
-                    self.generics.pop();
+    fn process_path(&mut self, node: &mut syn::Path) -> syn::Result<()> {
+        // `replace_ident("BufReader", async = "tokio::io::BufReader")`: `BufReader::new(..)`, but
+        // not the converted item itself, which is renamed as usual
+        let first = node.segments[0].ident.to_string();
+        if node.leading_colon.is_none()
+            && !self.is_shadowed(&first)
+            && self.params.original_self_name_get() != Some(first.as_str())
+        {
+            let replacement = self
+                .params
+                .replace_ident_get(&first)
+                .and_then(|record| record.get(self.convert_mode));
+            if let Some(replacement) = replacement {
+                let mut path = replacement.clone();
+                let first = node.segments.iter().next().unwrap();
+                if let Some(last) = path.segments.last_mut() {
+                    last.ident.set_span(first.ident.span());
+                    last.arguments = first.arguments.clone();
                 }
+                path.segments.extend(node.segments.iter().skip(1).cloned());
+                *node = path;
+            }
+        }
+
+        // `replace_path("std::sync::Mutex", async = "tokio::sync::Mutex")`: `std::sync::Mutex<T>`
+        if !self.params.replace_path_is_empty() {
+            self.replace_path(node)?;
+        }
+
+        // rename the last segment of a qualified path listed in `idents`: `a::Client`
+        let len = node.segments.len();
+        if len > 1 {
+            let names = node.segments.iter().map(|s| s.ident.to_string()).collect::<Vec<_>>();
+            for start in 0..len - 1 {
+                if let Some(ir) = self.params.idents_get(names[start..].join("::")) {
+                    let last = &node.segments[len - 1].ident;
+                    let renamed = ir.ident_add_suffix(last, self.convert_mode, self.params.key_get());
+                    self.report_rename(last, &renamed);
+                    node.segments[len - 1].ident = renamed;
+                    break;
+                }
+            }
+        }
+
+        // rename the leading crate segment of a qualified path: `tokio::time::Duration`
+        if node.segments.len() > 1 {
+            let first = &mut node.segments[0].ident;
+            if let Some(name) = self.params.rename_crate_get(first.to_string()) {
+                *first = syn::Ident::new(name, first.span());
             }
-            _ => {}
         }
+
         Ok(())
     }
 
+    fn process_const_expr(&mut self, node: &mut syn::Expr) -> syn::Result<()> {
+        check_const_context(node)
+    }
+
     fn process_path_segment(&mut self, node: &mut syn::PathSegment) -> syn::Result<()> {
-        let ident = &mut node.ident;
-        let ident_s = ident.to_string();
+        // Foo::<{ N }>
+        if let syn::PathArguments::AngleBracketed(args) = &node.arguments {
+            for arg in &args.args {
+                if let syn::GenericArgument::Const(expr) = arg {
+                    check_const_context(expr)?;
+                }
+            }
+        }
 
+        Ok(())
+    }
+
+    fn process_type(&mut self, node: &mut syn::Type) -> syn::Result<()> {
         // replace generic type with target type
-        if let Some(ps) = self.generics_get(&ident_s) {
-            *node = ps.clone();
-            return Ok(());
+        if let Some(ident) = bounded_generic_ident(node) {
+            if let Some(ty) = self.generics_get(ident.to_string()) {
+                *node = ty.clone();
+            }
+        }
+
+        match self.convert_mode {
+            ConvertMode::IntoSync => match node {
+                // &'async_trait T -> &T
+                syn::Type::Reference(reference) => {
+                    if matches!(&reference.lifetime, Some(lt) if is_async_trait_lifetime(lt)) {
+                        reference.lifetime = None;
+                    }
+                }
+                // dyn Trait + 'async_trait -> dyn Trait
+                syn::Type::TraitObject(object) => {
+                    strip_bounds(&mut object.bounds, is_async_trait_bound);
+                }
+                syn::Type::ImplTrait(impl_trait) => {
+                    strip_bounds(&mut impl_trait.bounds, is_async_trait_bound);
+                }
+                _ => {}
+            },
+            ConvertMode::IntoAsync => {}
         }
 
         Ok(())
@@ -363,8 +1277,19 @@ impl<'p> AsyncAwaitVisitor<'p> {
             return Ok(());
         };
 
+        // generic parameters in scope shadow entries of the idents map
+        if self.is_shadowed(ident.to_string()) {
+            return Ok(());
+        }
+
         if let Some(ir) = self.params.idents_get(ident.to_string()) {
-            *ident = ir.ident_add_suffix(ident, self.convert_mode, self.params.key_get());
+            // methods of other receivers are renamed only on request, they may be foreign types
+            if mode == (IdentMode::Method { on_self: false }) && !ir.method_mode {
+                return Ok(());
+            }
+            let renamed = ir.ident_add_suffix(ident, self.convert_mode, self.params.key_get());
+            self.report_rename(ident, &renamed);
+            *ident = renamed;
             return Ok(());
         }
 
@@ -372,10 +1297,104 @@ impl<'p> AsyncAwaitVisitor<'p> {
     }
 
     fn process_type_param(&mut self, node: &mut syn::TypeParam) -> syn::Result<()> {
+        match self.convert_mode {
+            ConvertMode::IntoSync => {
+                // T: Send + 'async_trait -> T: Send
+                if strip_bounds(&mut node.bounds, is_async_trait_bound) {
+                    node.colon_token = None;
+                }
+            }
+            ConvertMode::IntoAsync => {}
+        }
+
         let ident = &mut node.ident;
 
+        if self.is_shadowed(ident.to_string()) {
+            return Ok(());
+        }
+
         if let Some(ir) = self.params.idents_get(&ident.to_string()) {
-            *ident = ir.ident_add_suffix(ident, self.convert_mode, self.params.key_get());
+            let renamed = ir.ident_add_suffix(ident, self.convert_mode, self.params.key_get());
+            self.report_rename(ident, &renamed);
+            *ident = renamed;
+        }
+
+        Ok(())
+    }
+
+    fn process_generics_scope(&mut self, node: &mut syn::Generics) -> syn::Result<()> {
+        self.enter_generics_scope(node, false)
+    }
+
+    fn process_fn_generics_scope(&mut self, node: &mut syn::Generics) -> syn::Result<()> {
+        self.enter_generics_scope(node, true)
+    }
+
+    fn enter_generics_scope(&mut self, node: &mut syn::Generics, fn_scope: bool) -> syn::Result<()> {
+        let mut names = HashSet::new();
+
+        for param in &node.params {
+            match param {
+                syn::GenericParam::Type(type_param) => {
+                    names.insert(type_param.ident.to_string());
+                }
+                syn::GenericParam::Const(const_param) => {
+                    names.insert(const_param.ident.to_string());
+                }
+                syn::GenericParam::Lifetime(_) => {}
+            }
+        }
+
+        self.shadowed.push(names);
+
+        // where Self: Send -> (removed)
+        if !self.params.drop_bounds_is_empty() {
+            let params = &self.params;
+            strip_generics_bounds(node, |bound| match bound {
+                syn::TypeParamBound::Trait(trait_bound) => match trait_bound.path.segments.last() {
+                    Some(segment) => params.drop_bounds_contains(&segment.ident.to_string()),
+                    None => false,
+                },
+                syn::TypeParamBound::Lifetime(_) => false,
+            });
+        }
+
+        match self.convert_mode {
+            ConvertMode::IntoSync => {
+                strip_async_trait_generics(node);
+
+                // find generic parameter of Future and replace it with its Output type, only in
+                // functions: generics of structs, impls and traits are kept as written
+                let gens = if fn_scope { strip_future_generics(node) } else { HashMap::new() };
+                self.generics.push(gens);
+            }
+            ConvertMode::IntoAsync => {}
+        }
+
+        Ok(())
+    }
+
+    fn after_process_generics_scope(&mut self, _node: &mut syn::Generics) -> syn::Result<()> {
+        self.shadowed.pop();
+
+        match self.convert_mode {
+            ConvertMode::IntoSync => {
+                self.generics.pop();
+            }
+            ConvertMode::IntoAsync => {}
+        }
+
+        Ok(())
+    }
+
+    fn after_process_field_value(&mut self, node: &mut syn::FieldValue) -> syn::Result<()> {
+        // shorthand `Foo { bar }` with renamed `bar` must be expanded to `Foo { bar: bar_sync }`
+        if node.colon_token.is_none() {
+            if let (syn::Member::Named(member), syn::Expr::Path(expr)) = (&node.member, &node.expr) {
+                if !expr.path.is_ident(member) {
+                    node.colon_token = Some(syn::Token![:](member.span()));
+                }
+            }
         }
 
         Ok(())
@@ -386,7 +1405,9 @@ impl<'p> AsyncAwaitVisitor<'p> {
             syn::UseTree::Path(syn::UsePath { ident, .. }) => {
                 if let Some(ir) = self.params.idents_get(&ident.to_string()) {
                     if !ir.use_mode {
-                        *ident = ir.ident_add_suffix(ident, self.convert_mode, self.params.key_get());
+                        let renamed = ir.ident_add_suffix(ident, self.convert_mode, self.params.key_get());
+                        self.report_rename(ident, &renamed);
+                        *ident = renamed;
                     }
                 }
             }
@@ -394,14 +1415,18 @@ impl<'p> AsyncAwaitVisitor<'p> {
                 let ident = &mut name.ident;
 
                 if let Some(ir) = self.params.idents_get(&ident.to_string()) {
-                    if ir.use_mode {
+                    let use_mode = ir.use_mode;
+                    let renamed = ir.ident_add_suffix(ident, self.convert_mode, self.params.key_get());
+                    self.report_rename(ident, &renamed);
+
+                    if use_mode {
                         *node = syn::UseTree::Rename(syn::UseRename {
                             ident: ident.clone(),
                             as_token: syn::Token![as](ident.span()),
-                            rename: ir.ident_add_suffix(ident, self.convert_mode, self.params.key_get()),
+                            rename: renamed,
                         });
                     } else {
-                        *ident = ir.ident_add_suffix(ident, self.convert_mode, self.params.key_get());
+                        *ident = renamed;
                     }
                 }
             }
@@ -419,24 +1444,27 @@ impl<'p> VisitMutExt for Visitor<AsyncAwaitVisitor<'p>> {
     fn process_ident(&mut self, ident: &mut syn::Ident, mode: IdentMode) -> syn::Result<()> {
         self.inner.process_ident(ident, mode)
     }
-    fn process_expr(&mut self, node: &mut syn::Expr) -> syn::Result<()> {
-        self.inner.process_expr(node)
-    }
-    fn process_item(&mut self, node: &mut syn::Item) -> syn::Result<()> {
-        self.inner.process_item(node)
+    fn process_const_expr(&mut self, node: &mut syn::Expr) -> syn::Result<()> {
+        self.inner.process_const_expr(node)
     }
-    fn after_process_item(&mut self, node: &mut syn::Item) -> syn::Result<()> {
-        self.inner.after_process_item(node)
+    fn process_expr(&mut self, node: &mut syn::Expr) -> syn::Result<()> {
+        self.inner.process_expr(node)?;
+
+        // syn does not descend into inline const blocks
+        if let syn::Expr::Verbatim(ts) = node {
+            if let Ok(mut inline_const) = syn::parse2::<InlineConst>(ts.clone()) {
+                self.visit_block_mut(&mut inline_const.block);
+                *ts = inline_const.into_token_stream();
+            }
+        }
+
+        Ok(())
     }
 
     fn process_macro(&mut self, node: &mut syn::Macro) -> syn::Result<()> {
-        if let Some(ident) = node.path.get_ident() {
-            if self
-                .inner
-                .params
-                .standard_macros()
-                .contains(&ident.to_string().as_str())
-            {
+        // `anyhow::anyhow!(...)` is treated the same way as `anyhow!(...)`
+        if let Some(ident) = node.path.segments.last().map(|s| &s.ident) {
+            if self.inner.params.transparent_macros_contains(&ident.to_string()) {
                 let mut args = syn::parse2::<PunctuatedList>(node.tokens.clone())?;
 
                 for arg in &mut args.list {
@@ -444,19 +1472,79 @@ impl<'p> VisitMutExt for Visitor<AsyncAwaitVisitor<'p>> {
                 }
 
                 node.tokens = args.list.into_token_stream();
+            } else if ident == "matches" {
+                let mut args = syn::parse2::<MatchesArgs>(node.tokens.clone())?;
+
+                self.visit_expr_mut(&mut args.expr);
+                if let Some((_, guard)) = &mut args.guard {
+                    self.visit_expr_mut(guard);
+                }
+
+                node.tokens = args.into_token_stream();
+            } else if ident == "stream" || ident == "try_stream" {
+                // `async_stream::stream! { ... }`: the body is a list of statements with `yield`s,
+                // other macros of the same name with another syntax are left unchanged
+                if let Ok(mut stmts) = syn::Block::parse_within.parse2(node.tokens.clone()) {
+                    for stmt in &mut stmts {
+                        self.visit_stmt_mut(stmt);
+                    }
+
+                    node.tokens = quote!(#(#stmts)*);
+                }
+            } else if matches!(self.inner.convert_mode, ConvertMode::IntoSync)
+                && self.inner.params.strip_macro_awaits_get()
+            {
+                // `vec![a.await]` and other macros with unknown syntax: only `.await` is removed
+                node.tokens = self.inner.strip_macro_awaits(std::mem::take(&mut node.tokens));
             }
         };
         Ok(())
     }
+    fn process_path(&mut self, node: &mut syn::Path) -> syn::Result<()> {
+        self.inner.process_path(node)
+    }
     fn process_path_segment(&mut self, node: &mut syn::PathSegment) -> syn::Result<()> {
         self.inner.process_path_segment(node)
     }
+    fn process_return_type(&mut self, node: &mut syn::ReturnType) -> syn::Result<()> {
+        self.inner.process_return_type(node)
+    }
+    fn process_signature(&mut self, node: &mut syn::Signature) -> syn::Result<()> {
+        self.inner.process_signature(node)
+    }
+    fn process_stmt(&mut self, node: &mut syn::Stmt) -> syn::Result<()> {
+        self.inner.process_stmt(node)
+    }
+    fn process_trait_item_type(&mut self, node: &mut syn::TraitItemType) -> syn::Result<()> {
+        self.inner.process_trait_item_type(node)
+    }
+    fn process_type(&mut self, node: &mut syn::Type) -> syn::Result<()> {
+        self.inner.process_type(node)
+    }
     fn process_type_param(&mut self, node: &mut syn::TypeParam) -> syn::Result<()> {
         self.inner.process_type_param(node)
     }
     fn process_use_tree(&mut self, node: &mut syn::UseTree) -> syn::Result<()> {
         self.inner.process_use_tree(node)
     }
+    fn after_process_field_value(&mut self, node: &mut syn::FieldValue) -> syn::Result<()> {
+        self.inner.after_process_field_value(node)
+    }
+    fn process_generics_scope(&mut self, node: &mut syn::Generics) -> syn::Result<()> {
+        self.inner.process_generics_scope(node)
+    }
+    fn process_fn_generics_scope(&mut self, node: &mut syn::Generics) -> syn::Result<()> {
+        self.inner.process_fn_generics_scope(node)
+    }
+    fn process_params_scope(&mut self, attrs: &mut Vec<syn::Attribute>) -> syn::Result<()> {
+        self.inner.process_params_scope(attrs)
+    }
+    fn after_process_params_scope(&mut self, attrs: &mut Vec<syn::Attribute>) -> syn::Result<()> {
+        self.inner.after_process_params_scope(attrs)
+    }
+    fn after_process_generics_scope(&mut self, node: &mut syn::Generics) -> syn::Result<()> {
+        self.inner.after_process_generics_scope(node)
+    }
 }
 
 impl<'p> AsyncAwaitVisitor<'p> {}
@@ -11,7 +11,7 @@ use crate::{
     params::MacroParameters,
     utils::{set_error_and_return, unwrap_or_set_error_and_return},
     visit_ext::{VisitMutExt, Visitor},
-    DEFAULT_CRATE_NAME, MACRO_MAYBE_NAME, MACRO_DEFAULT_NAME,
+    MACRO_MAYBE_NAME, MACRO_DEFAULT_NAME,
 };
 
 pub struct ContentVisitor {
@@ -124,7 +124,8 @@ impl ContentVisitor {
                 }
                 2 => {
                     if let TokenTree::Ident(ident) = &tt {
-                        if ident.to_string() == DEFAULT_CRATE_NAME {
+                        // nested `maybe` must use the same prefix as `default`
+                        if ident == self.params.prefix_get() {
                             result.extend(vec![tt]);
                             state = 3;
                             continue;
@@ -0,0 +1,41 @@
+use std::{path::Path, process::Command};
+
+// Let chains need edition 2024, but the UI tests are built with the edition of this crate, so
+// tests/edition2024/*.rs are built and run as a separate crate.
+fn run(name: &str) {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("edition2024");
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+
+    let manifest = format!(
+        "[package]\n\
+         name = \"maybe-async-cfg-edition2024\"\n\
+         version = \"0.0.0\"\n\
+         edition = \"2024\"\n\
+         publish = false\n\
+         \n\
+         [dependencies]\n\
+         maybe-async-cfg = {{ path = {:?} }}\n\
+         \n\
+         [workspace]\n",
+        manifest_dir,
+    );
+    std::fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+    if let Ok(lock) = std::fs::read(manifest_dir.join("Cargo.lock")) {
+        std::fs::write(dir.join("Cargo.lock"), lock).unwrap();
+    }
+    let source = std::fs::read_to_string(manifest_dir.join("tests/edition2024").join(name)).unwrap();
+    std::fs::write(dir.join("src/main.rs"), source).unwrap();
+
+    let status = Command::new(env!("CARGO"))
+        .args(["run", "--quiet"])
+        .current_dir(&dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "tests/edition2024/{} failed", name);
+}
+
+#[test]
+fn let_chains() {
+    run("let-chains.rs");
+}
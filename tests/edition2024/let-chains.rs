@@ -0,0 +1,21 @@
+#[maybe_async_cfg::maybe(idents(get(fn)), sync(), async())]
+async fn get(v: u32) -> Option<u32> {
+    Some(v)
+}
+
+// the awaits of the scrutinees are removed in the sync version, the chain is kept
+#[maybe_async_cfg::maybe(idents(get(fn), check(fn)), sync(), async())]
+async fn check(v: u32) -> bool {
+    if let Some(x) = get(v).await && x > 1 && let Some(y) = get(x).await {
+        y == v
+    } else {
+        false
+    }
+}
+
+fn main() {
+    assert!(check_sync(2));
+    assert!(!check_sync(1));
+    // the async version is only built
+    let _ = check_async(2);
+}
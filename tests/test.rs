@@ -1,5 +1,10 @@
 #[test]
 fn ui() {
+    // read back by tests/ui/96-report.rs and tests/ui/98-dump-stats.rs
+    let tmp = std::path::Path::new(env!("CARGO_TARGET_TMPDIR"));
+    std::env::set_var("MAYBE_ASYNC_CFG_REPORT", tmp.join("maybe-async-cfg-report.jsonl"));
+    std::env::set_var("MAYBE_ASYNC_CFG_DUMP_DIR", tmp);
+
     let t = trybuild::TestCases::new();
     t.pass("tests/ui/01-maybe-async.rs");
     t.pass("tests/ui/02-must-be-async.rs");
@@ -7,9 +12,117 @@ fn ui() {
     t.pass("tests/ui/04-unit-test-util.rs");
     t.pass("tests/ui/05-replace-future-generic-type-with-output.rs");
     t.pass("tests/ui/06-sync_impl_async_impl.rs");
+    t.pass("tests/ui/07-generic-shadows-ident.rs");
+    t.pass("tests/ui/08-rename-crate.rs");
+    t.pass("tests/ui/09-async-recursion.rs");
+    t.pass("tests/ui/10-format-macro-args.rs");
+    t.pass("tests/ui/11-key-cfg.rs");
+    t.pass("tests/ui/12-iterator-into-stream.rs");
+    t.pass("tests/ui/13-drop-args.rs");
+    t.pass("tests/ui/14-dyn-cast.rs");
+    t.pass("tests/ui/15-inline-const.rs");
+    t.pass("tests/ui/16-method-template.rs");
+    t.pass("tests/ui/17-gat-future-bound.rs");
+    t.pass("tests/ui/18-define-template.rs");
+    t.pass("tests/ui/19-control-flow-values.rs");
+    t.pass("tests/ui/20-send-without-async-methods.rs");
+    t.pass("tests/ui/21-fn-bound-future.rs");
+    t.pass("tests/ui/22-content-custom-prefix.rs");
+    t.pass("tests/ui/23-trim-cfg.rs");
+    t.pass("tests/ui/24-closure-in-collection.rs");
+    t.pass("tests/ui/25-struct-expr-fields.rs");
+    t.pass("tests/ui/26-generic-default.rs");
+    t.pass("tests/ui/27-collect-await.rs");
+    t.pass("tests/ui/28-qualified-idents.rs");
+    t.pass("tests/ui/29-fn-qualifiers.rs");
+    t.pass("tests/ui/30-labeled-loops.rs");
+    t.pass("tests/ui/31-body-per-mode.rs");
+    t.pass("tests/ui/32-await-in-error-macros.rs");
+    t.pass("tests/ui/33-raw-per-mode.rs");
+    t.pass("tests/ui/34-box-future.rs");
+    t.pass("tests/ui/35-async-block-argument.rs");
+    t.pass("tests/ui/36-async-trait-lifetime.rs");
+    t.pass("tests/ui/37-drop-bounds.rs");
+    t.pass("tests/ui/38-default-method-calls.rs");
+    t.pass("tests/ui/39-async-trait-cfg.rs");
+    t.pass("tests/ui/40-method-chain-try.rs");
+    t.pass("tests/ui/41-associated-const-path.rs");
+    t.pass("tests/ui/42-self-per-key.rs");
+    t.pass("tests/ui/43-guard-acquisition.rs");
+    t.pass("tests/ui/44-as-name.rs");
+    t.pass("tests/ui/45-keep-asyncness.rs");
+    t.pass("tests/ui/46-matches-guard.rs");
+    t.pass("tests/ui/47-external-trait-impl.rs");
+    t.pass("tests/ui/48-track-caller-sync.rs");
+    t.pass("tests/ui/49-double-try.rs");
+    t.pass("tests/ui/50-reexport.rs");
+    t.pass("tests/ui/51-paren-and-group.rs");
+    t.pass("tests/ui/52-version-prefix.rs");
+    t.pass("tests/ui/53-drop-pin.rs");
+    t.pass("tests/ui/54-turbofish-await.rs");
+    t.pass("tests/ui/55-force.rs");
+    t.pass("tests/ui/56-async-stream-body.rs");
+    t.pass("tests/ui/57-idents-merge.rs");
+    t.pass("tests/ui/58-impl-trait-argument.rs");
+    t.pass("tests/ui/59-type-alias-call-path.rs");
+    t.pass("tests/ui/60-desugared-default-method.rs");
+    t.pass("tests/ui/61-macro-path.rs");
+    t.pass("tests/ui/62-const-static.rs");
+    t.pass("tests/ui/63-type-alias.rs");
+    t.pass("tests/ui/64-macro-rules.rs");
+    t.pass("tests/ui/65-send-with-prefix.rs");
+    t.pass("tests/ui/66-replace-ident.rs");
+    t.pass("tests/ui/67-replace-path.rs");
+    t.pass("tests/ui/68-native-async-trait.rs");
+    t.pass("tests/ui/69-trait-variant.rs");
+    t.pass("tests/ui/70-drop-attrs-path.rs");
+    t.pass("tests/ui/71-add-attrs.rs");
+    t.pass("tests/ui/72-macro-await.rs");
+    t.pass("tests/ui/73-keep-await.rs");
+    t.pass("tests/ui/74-rewrite-spawn.rs");
+    t.pass("tests/ui/75-rewrite-sleep.rs");
+    t.pass("tests/ui/76-sync-via-block-on.rs");
+    t.pass("tests/ui/77-stream-to-iterator.rs");
+    t.pass("tests/ui/78-auto-cfg.rs");
+    t.pass("tests/ui/79-replace-feature-predicate.rs");
+    t.pass("tests/ui/80-trait-const-default.rs");
+    t.pass("tests/ui/81-keep-transitive.rs");
+    t.pass("tests/ui/82-nested-our-attrs.rs");
+    t.pass("tests/ui/83-not-send-spawn.rs");
+    t.pass("tests/ui/84-replace-feature-version-cfg.rs");
+    t.pass("tests/ui/85-dump.rs");
+    t.pass("tests/ui/86-impl-future-return.rs");
+    t.pass("tests/ui/87-rewrite-boxed.rs");
+    t.pass("tests/ui/88-async-closure.rs");
+    t.pass("tests/ui/89-doc-per-version.rs");
+    t.pass("tests/ui/90-vis.rs");
+    t.pass("tests/ui/91-transparent-macros.rs");
+    t.pass("tests/ui/92-runtime-attrs.rs");
+    t.pass("tests/ui/93-conditional-attrs.rs");
+    t.pass("tests/ui/94-foreign-method-call.rs");
+    t.pass("tests/ui/95-future-generics-scope.rs");
+    t.pass("tests/ui/96-report.rs");
+    t.pass("tests/ui/97-serialized-params.rs");
+    t.pass("tests/ui/98-dump-stats.rs");
+    t.pass("tests/ui/99-foreign-stream-macro.rs");
+    t.pass("tests/ui/100-send-prefix-round-trip.rs");
+    t.pass("tests/ui/101-replace-ident-self.rs");
+    t.pass("tests/ui/102-method-params-scope.rs");
 
     t.compile_fail("tests/ui/test_fail/01-empty-test.rs");
     t.compile_fail("tests/ui/test_fail/02-unknown-path.rs");
     t.compile_fail("tests/ui/test_fail/03-async-gt2.rs");
     t.compile_fail("tests/ui/test_fail/04-bad-sync-cond.rs");
+    t.compile_fail("tests/ui/test_fail/05-strict-params-typo.rs");
+    t.compile_fail("tests/ui/test_fail/06-disable-with-versions.rs");
+    t.compile_fail("tests/ui/test_fail/07-renamed-ident-span.rs");
+    t.compile_fail("tests/ui/test_fail/08-raw-not-items.rs");
+    t.compile_fail("tests/ui/test_fail/09-await-in-const-context.rs");
+    t.compile_fail("tests/ui/test_fail/10-unsupported-item.rs");
+    t.compile_fail("tests/ui/test_fail/11-undeclared-feature.rs");
+    t.compile_fail("tests/ui/test_fail/12-conditional-attrs-differ.rs");
+    t.compile_fail("tests/ui/test_fail/13-unsupported-item-abort.rs");
+    t.compile_fail("tests/ui/test_fail/14-trait-variant-send.rs");
+    t.compile_fail("tests/ui/test_fail/15-nested-maybe-versions.rs");
+    t.compile_fail("tests/ui/test_fail/16-drop-attrs-not-path.rs");
 }
@@ -0,0 +1,36 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(sync(), async())]
+pub struct Client;
+
+#[maybe_async_cfg::maybe(idents(Client), sync(), async())]
+pub struct Pool;
+
+#[maybe_async_cfg::maybe(keep_self, idents(Client, Pool), sync(), async())]
+impl Pool {
+    // `Client` here is a generic parameter and must not be renamed
+    pub async fn with<Client: Default>(&self) -> Client {
+        Client::default()
+    }
+
+    pub async fn client(&self) -> Client {
+        Client
+    }
+}
+
+#[maybe_async_cfg::maybe(keep_self, idents(Client, Pool), sync())]
+impl Pool {
+    // if the generic parameter were renamed, it would shadow the `ClientSync` struct
+    pub fn probe<Client: Default>(&self) -> (Client, ClientSync) {
+        (Client::default(), ClientSync)
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    let _: u32 = PoolSync.with::<u32>();
+    let _: ClientSync = PoolSync.client();
+    let _: (u32, ClientSync) = PoolSync.probe::<u32>();
+    let _: u32 = PoolAsync.with::<u32>().await;
+    let _: ClientAsync = PoolAsync.client().await;
+}
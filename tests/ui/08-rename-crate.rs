@@ -0,0 +1,12 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(keep_self, sync(rename_crate(tokio = "std")))]
+async fn timeout() -> tokio::time::Duration {
+    let base: tokio::time::Duration = tokio::time::Duration::from_secs(1);
+    base * 2
+}
+
+fn main() {
+    let d: std::time::Duration = timeout();
+    assert_eq!(d, std::time::Duration::from_secs(2));
+}
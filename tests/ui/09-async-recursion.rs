@@ -0,0 +1,37 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(sync(), async())]
+async fn fib(n: u64) -> u64 {
+    if n < 2 {
+        n
+    } else {
+        Box::pin(fib(n - 1)).await + Box::pin(fib(n - 2)).await
+    }
+}
+
+#[maybe_async_cfg::maybe(sync(), async())]
+struct Tree {
+    children: Vec<Tree>,
+}
+
+#[maybe_async_cfg::maybe(idents(Tree, depth(fn, method)), sync(), async())]
+impl Tree {
+    async fn depth(&self) -> usize {
+        let mut max = 0;
+        for child in &self.children {
+            max = max.max(Box::pin(child.depth()).await);
+        }
+        max + 1
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(fib_sync(10), 55);
+    assert_eq!(fib_async(10).await, 55);
+
+    let tree = TreeSync { children: vec![TreeSync { children: vec![] }] };
+    assert_eq!(tree.depth_sync(), 2);
+    let tree = TreeAsync { children: vec![TreeAsync { children: vec![] }] };
+    assert_eq!(tree.depth_async().await, 2);
+}
@@ -0,0 +1,26 @@
+#![allow(dead_code)]
+
+struct ThingSync;
+impl ThingSync {
+    const KIND: &'static str = "sync";
+}
+
+struct ThingAsync;
+impl ThingAsync {
+    const KIND: &'static str = "async";
+}
+
+#[maybe_async_cfg::maybe(idents(Thing), sync(), async())]
+async fn describe() -> String {
+    // the format string itself is left as is
+    let mut out = format!("Thing:{}", Thing::KIND);
+    use std::fmt::Write;
+    write!(out, "/{}", Thing::KIND).unwrap();
+    out
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(describe_sync(), "Thing:sync/sync");
+    assert_eq!(describe_async().await, "Thing:async/async");
+}
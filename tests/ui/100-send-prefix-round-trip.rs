@@ -0,0 +1,39 @@
+#![allow(dead_code)]
+
+extern crate maybe_async_cfg as mac;
+
+use std::rc::Rc;
+
+// Top-level `prefix` and `send` are written into the parameters of each version and parsed back
+// there: `mac::body!` is found only with the prefix, and the `Rc` held across `.await` compiles
+// only with `async_trait(?Send)` added for `send`.
+#[mac::maybe(prefix = "mac", idents(Store), sync(), async("async_trait::async_trait(?Send)"))]
+trait Store {
+    async fn get(&self) -> u32;
+}
+
+struct Local;
+
+#[mac::maybe(prefix = "mac", send = "?Send", idents(Store), keep_self, sync(), async())]
+impl Store for Local {
+    async fn get(&self) -> u32 {
+        mac::body!(
+            sync = {
+                let value = 1;
+                value
+            },
+            async = {
+                let value = Rc::new(2);
+                async {}.await;
+                *value
+            },
+        )
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(StoreSync::get(&Local), 1);
+    let store: Box<dyn StoreAsync> = Box::new(Local);
+    assert_eq!(store.get().await, 2);
+}
@@ -0,0 +1,39 @@
+#![allow(dead_code)]
+
+mod nonblocking {
+    pub struct Buffer(pub Vec<u8>);
+}
+
+// The same `replace_ident` is given to every item, but the item named `Buffer` and its `impl` are
+// renamed as usual instead of being replaced with `nonblocking::Buffer`
+#[maybe_async_cfg::maybe(replace_ident("Buffer", async = "nonblocking::Buffer"), sync(), async())]
+struct Buffer(Vec<u8>);
+
+#[maybe_async_cfg::maybe(replace_ident("Buffer", async = "nonblocking::Buffer"), sync(), async())]
+impl Buffer {
+    fn new(len: usize) -> Buffer {
+        Buffer(vec![0; len])
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    replace_ident("Buffer", sync = "BufferSync", async = "nonblocking::Buffer"),
+    sync(),
+    async(),
+)]
+async fn wrap(data: Vec<u8>) -> Buffer {
+    Buffer(data)
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(BufferSync::new(2).len(), 2);
+    assert_eq!(BufferAsync::new(3).len(), 3);
+    let BufferSync(sync) = wrap_sync(vec![1]);
+    let nonblocking::Buffer(async_) = wrap_async(vec![1, 2]).await;
+    assert_eq!(sync.len() + async_.len(), 3);
+}
@@ -0,0 +1,40 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(fetch(fn)), sync(), async())]
+async fn fetch() -> u32 {
+    1
+}
+
+#[maybe_async_cfg::maybe(sync(), async())]
+struct Store;
+
+// `fetch` is renamed only in `get`, `get_raw` calls the sync function in both versions
+#[maybe_async_cfg::maybe(sync(), async())]
+impl Store {
+    #[maybe_async_cfg::maybe(idents(fetch(fn)))]
+    async fn get(&self) -> u32 {
+        fetch().await + 1
+    }
+
+    async fn get_raw(&self) -> u32 {
+        fetch_sync()
+    }
+}
+
+#[maybe_async_cfg::maybe(sync(), async())]
+trait Source {
+    #[maybe_async_cfg::maybe(idents(fetch(fn)))]
+    async fn value(&self) -> u32 {
+        fetch().await * 10
+    }
+}
+
+impl SourceSync for StoreSync {}
+impl SourceAsync for StoreAsync {}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(StoreSync.get() + StoreSync.get_raw(), 3);
+    assert_eq!(StoreAsync.get().await + StoreAsync.get_raw().await, 3);
+    assert_eq!(StoreSync.value() + StoreAsync.value().await, 20);
+}
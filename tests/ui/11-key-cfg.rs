@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(key_cfg, sync(key = "is_sync"), async(key = "is_async"))]
+async fn value() -> u32 {
+    1
+}
+
+// Features `is_sync` and `is_async` are never enabled, so both versions above must be gated out
+#[cfg(not(feature = "is_sync"))]
+fn value_sync() -> u32 {
+    2
+}
+
+#[maybe_async_cfg::maybe(key_cfg, sync(key = "is_sync", not(feature = "is_async")), async())]
+async fn other() -> u32 {
+    3
+}
+
+#[cfg(not(all(not(feature = "is_async"), feature = "is_sync")))]
+fn other_sync() -> u32 {
+    4
+}
+
+fn main() {
+    assert_eq!(value_sync(), 2);
+    assert_eq!(other_sync(), 4);
+}
@@ -0,0 +1,30 @@
+#![allow(dead_code)]
+
+mod stream {
+    pub trait Stream {
+        type Item;
+        fn next(&mut self) -> Option<Self::Item>;
+    }
+
+    impl<I: Iterator> Stream for I {
+        type Item = I::Item;
+        fn next(&mut self) -> Option<Self::Item> {
+            Iterator::next(self)
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(sync(), async(rewrite_stream = "stream::Stream"))]
+fn numbers() -> impl Iterator<Item = u32> {
+    vec![1, 2, 3].into_iter()
+}
+
+fn main() {
+    use stream::Stream;
+
+    assert_eq!(numbers_sync().sum::<u32>(), 6);
+
+    // `next` would be ambiguous if the return type were still `impl Iterator`
+    let mut s = numbers_async();
+    assert_eq!(s.next(), Some(1));
+}
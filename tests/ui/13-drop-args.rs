@@ -0,0 +1,19 @@
+#![allow(dead_code)]
+
+use std::task::Context;
+
+pub struct Reader {
+    data: Vec<u8>,
+}
+
+#[maybe_async_cfg::maybe(keep_self, idents(size(fn)), sync(drop_args(cx)), async())]
+impl Reader {
+    pub async fn size(&self, cx: &mut Context<'_>) -> usize {
+        self.data.len()
+    }
+}
+
+fn main() {
+    let reader = Reader { data: vec![1, 2, 3] };
+    assert_eq!(reader.size_sync(), 3);
+}
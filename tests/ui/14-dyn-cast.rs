@@ -0,0 +1,26 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(sync(), async(async_trait::async_trait))]
+trait Service {
+    async fn call(&self) -> u32;
+}
+
+struct Echo(u32);
+
+#[maybe_async_cfg::maybe(keep_self, idents(Service), sync(), async(async_trait::async_trait))]
+impl Service for Echo {
+    async fn call(&self) -> u32 {
+        self.0
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Service), sync(), async())]
+async fn call_boxed(obj: Box<Echo>) -> u32 {
+    (obj.as_ref() as &dyn Service).call().await
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(call_boxed_sync(Box::new(Echo(1))), 1);
+    assert_eq!(call_boxed_async(Box::new(Echo(2))).await, 2);
+}
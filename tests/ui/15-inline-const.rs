@@ -0,0 +1,23 @@
+#![allow(dead_code)]
+
+struct LimitsSync;
+impl LimitsSync {
+    const SIZE: usize = 1;
+}
+
+struct LimitsAsync;
+impl LimitsAsync {
+    const SIZE: usize = 2;
+}
+
+#[maybe_async_cfg::maybe(idents(Limits), sync(), async())]
+async fn buffer() -> [u8; 4] {
+    let size = const { Limits::SIZE * 2 };
+    [size as u8; 4]
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(buffer_sync(), [2; 4]);
+    assert_eq!(buffer_async().await, [4; 4]);
+}
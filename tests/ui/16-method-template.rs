@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+pub struct Conn {
+    id: u32,
+}
+
+impl Conn {
+    pub async fn open_async(&self, retries: u32) -> u32 {
+        self.id + retries
+    }
+
+    pub fn open(conn: &Conn, retries: u32) -> u32 {
+        conn.id * retries
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(connect(fn)),
+    sync(methods(open_async = "Conn::open($self, $args)")),
+    async()
+)]
+async fn connect(conn: &Conn) -> u32 {
+    conn.open_async(3).await
+}
+
+#[async_std::main]
+async fn main() {
+    let conn = Conn { id: 2 };
+    assert_eq!(connect_sync(&conn), 6);
+    assert_eq!(connect_async(&conn).await, 5);
+}
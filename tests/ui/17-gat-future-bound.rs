@@ -0,0 +1,38 @@
+#![allow(dead_code)]
+
+use std::future::Future;
+
+#[maybe_async_cfg::maybe(idents(Fetch), sync(), async())]
+pub trait Fetch {
+    type Fut<'a>: Future<Output = u32> + 'a
+    where
+        Self: 'a;
+
+    fn fetch<'a>(&'a self) -> Self::Fut<'a>;
+}
+
+struct Value(u32);
+
+impl FetchSync for Value {
+    // no Future bound in the sync version, the lifetime parameter is kept
+    type Fut<'a> = &'a u32;
+
+    fn fetch<'a>(&'a self) -> &'a u32 {
+        &self.0
+    }
+}
+
+impl FetchAsync for Value {
+    type Fut<'a> = std::future::Ready<u32>;
+
+    fn fetch<'a>(&'a self) -> Self::Fut<'a> {
+        std::future::ready(self.0)
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    let value = Value(7);
+    assert_eq!(*FetchSync::fetch(&value), 7);
+    assert_eq!(FetchAsync::fetch(&value).await, 7);
+}
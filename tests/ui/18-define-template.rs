@@ -0,0 +1,32 @@
+#![allow(dead_code)]
+
+maybe_async_cfg::define!(versions, idents(Store, load(fn)), sync(), async());
+
+#[maybe_async_cfg::maybe(use = "versions")]
+pub struct Store {
+    value: u32,
+}
+
+#[maybe_async_cfg::maybe(use = "versions")]
+impl Store {
+    pub async fn load(&self) -> u32 {
+        self.value
+    }
+}
+
+maybe_async_cfg::define!(names, idents(Store, load(fn, method)));
+maybe_async_cfg::define!(both, sync(), async());
+
+// several templates are inserted in place of each `use`
+#[maybe_async_cfg::maybe(use = "names", idents(total(fn)), use = "both")]
+pub async fn total(store: &Store) -> u32 {
+    store.load().await * 2
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(StoreSync { value: 1 }.load_sync(), 1);
+    assert_eq!(StoreAsync { value: 2 }.load_async().await, 2);
+    assert_eq!(total_sync(&StoreSync { value: 3 }), 6);
+    assert_eq!(total_async(&StoreAsync { value: 4 }).await, 8);
+}
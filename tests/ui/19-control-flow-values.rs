@@ -0,0 +1,48 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(value(fn)), sync(), async())]
+async fn value(n: u32) -> u32 {
+    n
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn), with_if(fn)), sync(), async())]
+async fn with_if(cond: bool) -> u32 {
+    let x = if cond { value(1).await } else { value(2).await };
+    x
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn), with_match(fn)), sync(), async())]
+async fn with_match(n: Option<u32>) -> u32 {
+    let x = match n {
+        Some(n) if n > 10 => value(n).await,
+        Some(n) => {
+            let m = value(n).await;
+            m * 2
+        }
+        None => value(0).await,
+    };
+    x
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn), with_loop(fn)), sync(), async())]
+async fn with_loop(limit: u32) -> u32 {
+    let mut i = 0;
+    let x = loop {
+        i = value(i).await + 1;
+        if i >= limit {
+            break value(i).await;
+        }
+    };
+    x
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(with_if_sync(true), 1);
+    assert_eq!(with_if_async(false).await, 2);
+    assert_eq!(with_match_sync(Some(11)), 11);
+    assert_eq!(with_match_sync(Some(3)), 6);
+    assert_eq!(with_match_async(None).await, 0);
+    assert_eq!(with_loop_sync(3), 3);
+    assert_eq!(with_loop_async(5).await, 5);
+}
@@ -0,0 +1,19 @@
+#![allow(dead_code)]
+
+// any use of `async_trait::async_trait` in this crate fails to resolve
+mod async_trait {}
+
+pub struct Counter {
+    value: u32,
+}
+
+#[maybe_async_cfg::maybe(keep_self, async(send = "Send"))]
+impl Counter {
+    pub fn get(&self) -> u32 {
+        self.value
+    }
+}
+
+fn main() {
+    assert_eq!(Counter { value: 3 }.get(), 3);
+}
@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+use std::future::Future;
+
+#[maybe_async_cfg::maybe(idents(call_twice(fn)), sync(), async())]
+async fn call_twice<F, Fut>(f: F) -> u32
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = u32>,
+{
+    f(1).await + f(2).await
+}
+
+#[maybe_async_cfg::maybe(idents(try_call(fn)), sync(), async())]
+async fn try_call<F, Fut>(f: F) -> std::io::Result<u32>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = std::io::Result<u32>>,
+{
+    f().await
+}
+
+#[async_std::main]
+async fn main() {
+    // sync version: F: Fn(u32) -> u32
+    assert_eq!(call_twice_sync(|x| x * 10), 30);
+    assert_eq!(call_twice_async(|x| async move { x * 10 }).await, 30);
+
+    assert_eq!(try_call_sync(|| Ok(5)).unwrap(), 5);
+    assert_eq!(try_call_async(|| async { Ok(5) }).await.unwrap(), 5);
+}
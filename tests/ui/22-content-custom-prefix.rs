@@ -0,0 +1,24 @@
+#![allow(dead_code)]
+
+use maybe_async_cfg as mac;
+
+pub struct ValueSync(u32);
+pub struct ValueAsync(u32);
+
+mac::content! {
+#![mac::default(idents(Value))]
+
+macro_rules! holder {
+    () => {
+        #[mac::maybe(sync(), async())]
+        pub struct Holder(pub Value);
+    };
+}
+
+holder!();
+}
+
+fn main() {
+    assert_eq!(HolderSync(ValueSync(1)).0 .0, 1);
+    assert_eq!(HolderAsync(ValueAsync(2)).0 .0, 2);
+}
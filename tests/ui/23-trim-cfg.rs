@@ -0,0 +1,43 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(flags(fn)), trim_cfg, sync(), async())]
+async fn flags() -> Vec<&'static str> {
+    let mut out = vec![];
+
+    // all(not(test), not(test)) -> not(test)
+    #[cfg(all(not(test), not(test)))]
+    out.push("dedup");
+
+    // all(not(test)) -> not(test)
+    #[cfg(all(not(test)))]
+    out.push("single");
+
+    // all(all(not(test)), all()) -> not(test)
+    #[cfg(all(all(not(test)), all()))]
+    out.push("flatten");
+
+    // any(all(), all()) -> all(), always true
+    #[cfg(any(all(), all()))]
+    out.push("always");
+
+    // all(any()) -> any(), always false
+    #[cfg(all(any()))]
+    out.push("never");
+
+    // any(any(), not(any())) -> not(any()), always true
+    #[cfg(any(any(), not(any())))]
+    out.push("not");
+
+    // not(all(test)) -> not(test)
+    #[cfg(not(all(test)))]
+    out.push("inner");
+
+    out
+}
+
+#[async_std::main]
+async fn main() {
+    let expected = vec!["dedup", "single", "flatten", "always", "not", "inner"];
+    assert_eq!(flags_sync(), expected);
+    assert_eq!(flags_async().await, expected);
+}
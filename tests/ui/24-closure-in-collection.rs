@@ -0,0 +1,24 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+#[maybe_async_cfg::maybe(idents(handle(fn)), sync(), async())]
+async fn handle(req: u32) -> u32 {
+    req + 1
+}
+
+// `rewrite_boxed` flattens the async block returned by the closure in the sync version
+#[maybe_async_cfg::maybe(idents(handle(fn), dispatch(fn)), rewrite_boxed, sync(), async())]
+async fn dispatch(name: &str, req: u32) -> u32 {
+    let mut handlers = HashMap::new();
+    handlers.insert("double", Box::new(|req| async move { handle(req).await * 2 }));
+
+    let handler = handlers.get(name).expect("unknown handler");
+    handler(req).await
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(dispatch_sync("double", 1), 4);
+    assert_eq!(dispatch_async("double", 2).await, 6);
+}
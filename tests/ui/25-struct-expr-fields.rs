@@ -0,0 +1,34 @@
+#![allow(dead_code, non_upper_case_globals)]
+
+#[derive(Debug, PartialEq)]
+pub struct Settings {
+    size: u32,
+    limit: u32,
+    name: &'static str,
+}
+
+#[maybe_async_cfg::maybe(idents(defaults(fn)), sync(), async())]
+async fn defaults() -> Settings {
+    Settings { size: 0, limit: 0, name: "default" }
+}
+
+static limit_sync: u32 = 10;
+static limit_async: u32 = 20;
+
+#[maybe_async_cfg::maybe(idents(load(fn)), sync(), async())]
+async fn load() -> u32 {
+    5
+}
+
+#[maybe_async_cfg::maybe(idents(load(fn), defaults(fn), settings(fn), limit(fn)), sync(), async())]
+async fn settings() -> Settings {
+    // await in a field value and in the struct update base, `limit` is expanded to
+    // `limit: limit_sync` (`limit: limit_async`)
+    Settings { size: load().await, limit, ..defaults().await }
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(settings_sync(), Settings { size: 5, limit: 10, name: "default" });
+    assert_eq!(settings_async().await, Settings { size: 5, limit: 20, name: "default" });
+}
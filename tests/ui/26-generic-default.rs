@@ -0,0 +1,17 @@
+#![allow(dead_code)]
+
+pub struct JobSync(u32);
+pub struct JobAsync(u64);
+
+#[maybe_async_cfg::maybe(idents(Job), sync(), async())]
+pub struct Task<J = Job> {
+    job: J,
+}
+
+fn main() {
+    // the default type parameter is renamed per version
+    let sync_task: TaskSync = TaskSync { job: JobSync(1) };
+    let async_task: TaskAsync = TaskAsync { job: JobAsync(2) };
+    assert_eq!(sync_task.job.0, 1);
+    assert_eq!(async_task.job.0, 2);
+}
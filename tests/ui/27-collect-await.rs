@@ -0,0 +1,36 @@
+#![allow(dead_code)]
+
+use std::iter::FromIterator;
+
+/// Minimal stream-like type with an async terminal operation.
+pub struct NumberStream(Vec<u32>);
+
+impl NumberStream {
+    pub fn map<F: Fn(u32) -> u32>(self, f: F) -> NumberStream {
+        NumberStream(self.0.into_iter().map(f).collect())
+    }
+
+    pub async fn collect<B: FromIterator<u32>>(self) -> B {
+        self.0.into_iter().collect()
+    }
+}
+
+fn numbers_sync() -> std::vec::IntoIter<u32> {
+    vec![1, 2, 3].into_iter()
+}
+
+fn numbers_async() -> NumberStream {
+    NumberStream(vec![1, 2, 3])
+}
+
+#[maybe_async_cfg::maybe(idents(numbers(fn), doubled(fn)), sync(), async())]
+async fn doubled() -> Vec<u32> {
+    // sync: numbers_sync().map(..).collect::<Vec<_>>()
+    numbers().map(|x| x * 2).collect::<Vec<_>>().await
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(doubled_sync(), vec![2, 4, 6]);
+    assert_eq!(doubled_async().await, vec![2, 4, 6]);
+}
@@ -0,0 +1,24 @@
+#![allow(dead_code)]
+
+mod a {
+    pub struct ClientSync(pub u32);
+    pub struct ClientAsync(pub u32);
+}
+
+mod b {
+    pub struct Client(pub u32);
+}
+
+#[maybe_async_cfg::maybe(idents(a::Client, clients(fn)), sync(), async())]
+async fn clients() -> (a::Client, b::Client) {
+    // only `a::Client` is renamed, `b::Client` is kept
+    (a::Client(1), b::Client(2))
+}
+
+#[async_std::main]
+async fn main() {
+    let (a::ClientSync(x), b::Client(y)) = clients_sync();
+    assert_eq!((x, y), (1, 2));
+    let (a::ClientAsync(x), b::Client(y)) = clients_async().await;
+    assert_eq!((x, y), (1, 2));
+}
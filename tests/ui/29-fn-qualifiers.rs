@@ -0,0 +1,25 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(raw_add(fn)), sync(), async())]
+pub const unsafe extern "C" fn raw_add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[maybe_async_cfg::maybe(idents(add(fn)), sync(), async())]
+#[inline]
+pub async fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+// const and extern "C" qualifiers survive the conversion
+const SUM: u32 = unsafe { raw_add_sync(1, 2) };
+const RAW: unsafe extern "C" fn(u32, u32) -> u32 = raw_add_sync;
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(SUM, 3);
+    assert_eq!(unsafe { RAW(2, 2) }, 4);
+    assert_eq!(unsafe { raw_add_async(2, 3) }, 5);
+    assert_eq!(add_sync(1, 1), 2);
+    assert_eq!(add_async(1, 2).await, 3);
+}
@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(value(fn)), sync(), async())]
+async fn value(n: u32) -> u32 {
+    n
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn), labeled(fn)), sync(), async())]
+async fn labeled() -> u32 {
+    let mut i = 0;
+    let x = 'outer: loop {
+        loop {
+            i += 1;
+            if i > 3 {
+                // break carrying an awaited value
+                break 'outer value(i).await;
+            }
+        }
+    };
+
+    let y = 'block: {
+        if value(x).await > 3 {
+            break 'block value(10).await;
+        }
+        0
+    };
+
+    x + y
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn), next(fn), drain(fn)), sync(), async())]
+async fn next(items: &mut Vec<u32>) -> Option<u32> {
+    items.pop()
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn), next(fn), drain(fn)), sync(), async())]
+async fn drain(mut items: Vec<u32>) -> u32 {
+    let mut sum = 0;
+    while let Some(item) = next(&mut items).await {
+        sum += value(item).await;
+    }
+    while value(sum).await < 10 {
+        sum += 1;
+    }
+    sum
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(labeled_sync(), 14);
+    assert_eq!(labeled_async().await, 14);
+    assert_eq!(drain_sync(vec![1, 2]), 10);
+    assert_eq!(drain_async(vec![5, 6]).await, 11);
+}
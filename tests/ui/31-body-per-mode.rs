@@ -0,0 +1,21 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(describe(fn)), sync(), async())]
+async fn describe(n: u32) -> String {
+    maybe_async_cfg::body!(
+        sync = {
+            let doubled = n * 2;
+            format!("sync {}", doubled)
+        },
+        async = {
+            let text = async { n.to_string() }.await;
+            format!("async {}", text)
+        },
+    )
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(describe_sync(2), "sync 4");
+    assert_eq!(describe_async(2).await, "async 2");
+}
@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+// Stand-ins for `anyhow::anyhow!` and `anyhow::bail!`
+macro_rules! anyhow {
+    ($($arg:tt)*) => { format!($($arg)*) };
+}
+
+macro_rules! bail {
+    ($($arg:tt)*) => { return Err(anyhow!($($arg)*)) };
+}
+
+#[maybe_async_cfg::maybe(idents(resolve(fn)), sync(), async())]
+async fn resolve() -> u32 {
+    42
+}
+
+#[maybe_async_cfg::maybe(idents(resolve(fn), check(fn)), sync(), async())]
+async fn check(fail: bool) -> Result<u32, String> {
+    if fail {
+        return Err(anyhow!("failed: {}", resolve().await));
+    }
+    if resolve().await != 42 {
+        bail!("unexpected: {}", resolve().await);
+    }
+    Ok(resolve().await)
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(check_sync(true), Err("failed: 42".to_string()));
+    assert_eq!(check_sync(false), Ok(42));
+    assert_eq!(check_async(true).await, Err("failed: 42".to_string()));
+    assert_eq!(check_async(false).await, Ok(42));
+}
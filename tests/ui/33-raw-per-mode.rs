@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+mod blocking {
+    pub fn read() -> u32 {
+        1
+    }
+}
+
+mod nonblocking {
+    pub async fn read() -> u32 {
+        2
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(read(fn), load(fn)), sync(), async())]
+#[maybe_async_cfg::raw(
+    sync = "use blocking::read as read_sync;",
+    async = "use nonblocking::read as read_async;"
+)]
+async fn load() -> u32 {
+    read().await
+}
+
+#[maybe_async_cfg::maybe(sync(), async())]
+#[maybe_async_cfg::raw(sync = "const SOURCE_SYNC: &str = \"blocking\";", async = "const SOURCE_ASYNC: &str = \"nonblocking\";")]
+const LIMIT: u32 = 3;
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(load_sync(), 1);
+    assert_eq!(load_async().await, 2);
+    assert_eq!((LIMIT_SYNC, SOURCE_SYNC), (3, "blocking"));
+    assert_eq!((LIMIT_ASYNC, SOURCE_ASYNC), (3, "nonblocking"));
+}
@@ -0,0 +1,40 @@
+#![allow(dead_code)]
+
+use std::{future::Future, pin::Pin};
+
+// The same as `futures::future::BoxFuture`
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+#[maybe_async_cfg::maybe(rewrite_boxed, idents(Source), sync(), async())]
+trait Source {
+    fn fetch(&self) -> BoxFuture<'_, u32>;
+    fn fetch_pinned(&self) -> Pin<Box<dyn Future<Output = u32> + Send + '_>>;
+}
+
+#[maybe_async_cfg::maybe(idents(Source, Constant), sync(), async())]
+struct Constant(u32);
+
+#[maybe_async_cfg::maybe(rewrite_boxed, idents(Source, Constant), sync(), async())]
+impl Source for Constant {
+    fn fetch(&self) -> BoxFuture<'_, u32> {
+        Box::pin(async move { self.0 })
+    }
+
+    fn fetch_pinned(&self) -> Pin<Box<dyn Future<Output = u32> + Send + '_>> {
+        Box::pin(async move {
+            let value = self.fetch().await;
+            value + 1
+        })
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    let source = ConstantSync(1);
+    assert_eq!(SourceSync::fetch(&source), 1);
+    assert_eq!(SourceSync::fetch_pinned(&source), 2);
+
+    let source = ConstantAsync(3);
+    assert_eq!(SourceAsync::fetch(&source).await, 3);
+    assert_eq!(SourceAsync::fetch_pinned(&source).await, 4);
+}
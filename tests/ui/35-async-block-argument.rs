@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+use std::future::Future;
+
+fn run_sync<T>(value: T) -> T {
+    value
+}
+
+async fn run_async<F: Future>(fut: F) -> F::Output {
+    fut.await
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn)), sync(), async())]
+async fn value() -> u32 {
+    5
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn), run(fn), spawned(fn)), sync(), async())]
+async fn spawned() -> u32 {
+    // a bare async block argument, without a wrapping closure
+    let first = run(async move { value().await }).await;
+    let second = run(async {
+        let x = value().await;
+        x * 2
+    })
+    .await;
+    first + second
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(spawned_sync(), 15);
+    assert_eq!(spawned_async().await, 15);
+}
@@ -0,0 +1,55 @@
+#![allow(dead_code)]
+
+use std::{future::Future, pin::Pin};
+
+// A signature in the shape produced by expanding `async_trait`
+#[maybe_async_cfg::maybe(rewrite_boxed, idents(Source), sync(), async())]
+trait Source {
+    fn fetch<'life0, 'async_trait>(
+        &'life0 self,
+        scale: &'async_trait u32,
+    ) -> Pin<Box<dyn Future<Output = u32> + Send + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: Sync + 'async_trait;
+}
+
+#[maybe_async_cfg::maybe(idents(Source, Constant), sync(), async())]
+struct Constant(u32);
+
+#[maybe_async_cfg::maybe(rewrite_boxed, idents(Source, Constant), sync(), async())]
+impl Source for Constant {
+    fn fetch<'life0, 'async_trait>(
+        &'life0 self,
+        scale: &'async_trait u32,
+    ) -> Pin<Box<dyn Future<Output = u32> + Send + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: Sync + 'async_trait,
+    {
+        Box::pin(async move { self.0 * *scale })
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Holder), sync(), async())]
+trait Holder {
+    fn value<'async_trait>(&'async_trait self) -> &'async_trait u32;
+}
+
+#[maybe_async_cfg::maybe(idents(Holder, Constant), sync(), async())]
+impl Holder for Constant {
+    fn value<'async_trait>(&'async_trait self) -> &'async_trait u32 {
+        &self.0
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    let source = ConstantSync(2);
+    assert_eq!(SourceSync::fetch(&source, &3), 6);
+    assert_eq!(*HolderSync::value(&source), 2);
+
+    let source = ConstantAsync(4);
+    assert_eq!(SourceAsync::fetch(&source, &3).await, 12);
+    assert_eq!(*HolderAsync::value(&source), 4);
+}
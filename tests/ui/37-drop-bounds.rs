@@ -0,0 +1,28 @@
+#![allow(dead_code)]
+
+use std::rc::Rc;
+
+#[maybe_async_cfg::maybe(idents(Counter), sync(drop_bounds(Send)), async())]
+struct Counter<T> {
+    value: T,
+}
+
+#[maybe_async_cfg::maybe(idents(Counter), sync(drop_bounds(Send, Sync)), async())]
+impl<T: Clone + Send + Sync> Counter<T> {
+    async fn get(&self) -> T
+    where
+        Self: Send,
+    {
+        self.value.clone()
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    // `Rc` is neither `Send` nor `Sync`, so the bounds must be dropped in the sync version
+    let counter = CounterSync { value: Rc::new(1) };
+    assert_eq!(*counter.get(), 1);
+
+    let counter = CounterAsync { value: 2 };
+    assert_eq!(counter.get().await, 2);
+}
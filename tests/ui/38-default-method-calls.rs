@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(
+    idents(Source, fetch(fn, sync = "fetch_blocking", async = "fetch")),
+    sync(),
+    async(),
+)]
+trait Source {
+    async fn fetch(&self) -> u32;
+
+    // a default method calling another renamed method of the trait
+    async fn fetch_twice(&self) -> u32 {
+        self.fetch().await + self.fetch().await
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(Source, Constant, fetch(fn, sync = "fetch_blocking", async = "fetch")),
+    sync(),
+    async(),
+)]
+struct Constant(u32);
+
+#[maybe_async_cfg::maybe(
+    idents(Source, Constant, fetch(fn, sync = "fetch_blocking", async = "fetch")),
+    sync(),
+    async(),
+)]
+impl Source for Constant {
+    async fn fetch(&self) -> u32 {
+        self.0
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    let source = ConstantSync(2);
+    assert_eq!(source.fetch_blocking(), 2);
+    assert_eq!(SourceSync::fetch_twice(&source), 4);
+
+    let source = ConstantAsync(3);
+    assert_eq!(source.fetch().await, 3);
+    assert_eq!(SourceAsync::fetch_twice(&source).await, 6);
+}
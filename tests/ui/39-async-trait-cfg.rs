@@ -0,0 +1,42 @@
+#![allow(dead_code)]
+
+// The condition holds: the async impl uses async_trait, like the trait
+#[maybe_async_cfg::maybe(idents(Boxed), sync(), async(outer(async_trait::async_trait)))]
+trait Boxed {
+    async fn fetch(&self) -> u32;
+}
+
+#[maybe_async_cfg::maybe(idents(Boxed, Constant), sync(), async(async_trait_cfg(all())))]
+impl Boxed for Constant {
+    async fn fetch(&self) -> u32 {
+        self.0
+    }
+}
+
+// The condition does not hold: the async impl is native, like the trait
+#[maybe_async_cfg::maybe(idents(Native), sync(), async())]
+trait Native {
+    async fn fetch(&self) -> u32;
+}
+
+#[maybe_async_cfg::maybe(idents(Native, Constant), sync(), async(async_trait_cfg(any())))]
+impl Native for Constant {
+    async fn fetch(&self) -> u32 {
+        self.0 + 1
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Constant), sync(), async())]
+struct Constant(u32);
+
+#[async_std::main]
+async fn main() {
+    let source = ConstantSync(1);
+    assert_eq!(BoxedSync::fetch(&source), 1);
+    assert_eq!(NativeSync::fetch(&source), 2);
+
+    let source = ConstantAsync(3);
+    let boxed: &(dyn BoxedAsync + Sync) = &source;
+    assert_eq!(boxed.fetch().await, 3);
+    assert_eq!(NativeAsync::fetch(&source).await, 4);
+}
@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+// A minimal stand-in for a reqwest-like HTTP client
+#[maybe_async_cfg::maybe(idents(Client, Request, Response), sync(), async())]
+struct Client;
+
+#[maybe_async_cfg::maybe(idents(Client, Request, Response), sync(), async())]
+struct Request {
+    url: String,
+    headers: Vec<String>,
+}
+
+#[maybe_async_cfg::maybe(idents(Client, Request, Response), sync(), async())]
+struct Response {
+    body: String,
+}
+
+#[maybe_async_cfg::maybe(idents(Client, Request), sync(), async())]
+impl Client {
+    fn get(&self, url: &str) -> Request {
+        Request { url: url.to_string(), headers: vec![] }
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Request, Response), sync(), async())]
+impl Request {
+    fn header(mut self, h: &str) -> Self {
+        self.headers.push(h.to_string());
+        self
+    }
+
+    async fn send(self) -> Result<Response, String> {
+        Ok(Response { body: format!("{} {}", self.url, self.headers.join(",")) })
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Response), sync(), async())]
+impl Response {
+    async fn json(self) -> Result<String, String> {
+        Ok(self.body)
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Client, fetch(fn)), sync(), async())]
+async fn fetch(client: &Client, url: &str, h: &str) -> Result<String, String> {
+    let body = client.get(url).header(h).send().await?.json().await?;
+    Ok(body)
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(fetch_sync(&ClientSync, "/a", "x").unwrap(), "/a x");
+    assert_eq!(fetch_async(&ClientAsync, "/b", "y").await.unwrap(), "/b y");
+}
@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(Limits), sync(), async())]
+struct Limits;
+
+#[maybe_async_cfg::maybe(idents(Limits), sync(), async())]
+impl Limits {
+    const MAX: u32 = 10;
+
+    async fn max() -> u32 {
+        // the type segment is renamed, the const name is kept
+        Limits::MAX
+    }
+
+    fn qualified() -> u32 {
+        <Limits>::MAX
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Limits, doubled(fn)), sync(), async())]
+async fn doubled() -> u32 {
+    Limits::MAX + Limits::max().await
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(LimitsSync::MAX, 10);
+    assert_eq!(LimitsAsync::qualified(), 10);
+    assert_eq!(doubled_sync(), 20);
+    assert_eq!(doubled_async().await, 20);
+}
@@ -0,0 +1,32 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(
+    sync(key = "blocking", self = "BlockingClient"),
+    async(key = "tokio", self = "TokioClient"),
+    async(key = "async_std", self = "AsyncStdClient"),
+)]
+struct Client {
+    id: u32,
+}
+
+#[maybe_async_cfg::maybe(
+    sync(key = "blocking", self = "BlockingClient"),
+    async(key = "tokio", self = "TokioClient"),
+    async(key = "async_std", self = "AsyncStdClient"),
+)]
+impl Client {
+    fn new(id: u32) -> Client {
+        Client { id }
+    }
+
+    async fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(BlockingClient::new(1).id(), 1);
+    assert_eq!(TokioClient::new(2).id().await, 2);
+    assert_eq!(AsyncStdClient::new(3).id().await, 3);
+}
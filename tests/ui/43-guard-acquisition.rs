@@ -0,0 +1,49 @@
+#![allow(dead_code)]
+
+use std::cell::Cell;
+
+#[maybe_async_cfg::maybe(idents(Lock, Guard), sync(), async())]
+struct Lock {
+    held: Cell<bool>,
+}
+
+#[maybe_async_cfg::maybe(idents(Lock, Guard), sync(), async())]
+struct Guard<'a> {
+    lock: &'a Lock,
+}
+
+#[maybe_async_cfg::maybe(idents(Lock, Guard), sync(), async())]
+impl Lock {
+    async fn acquire(&self) -> Guard<'_> {
+        self.held.set(true);
+        Guard { lock: self }
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Guard), sync(), async())]
+impl<'a> Drop for Guard<'a> {
+    fn drop(&mut self) {
+        self.lock.held.set(false);
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Lock, Guard, critical(fn)), sync(), async())]
+async fn critical(lock: &Lock) -> bool {
+    let _g: Guard<'_> = lock.acquire().await;
+    let held = lock.held.get();
+    {
+        let _inner = lock.acquire().await;
+    }
+    held && !lock.held.get()
+}
+
+#[async_std::main]
+async fn main() {
+    let lock = LockSync { held: Cell::new(false) };
+    assert!(critical_sync(&lock));
+    assert!(!lock.held.get());
+
+    let lock = LockAsync { held: Cell::new(false) };
+    assert!(critical_async(&lock).await);
+    assert!(!lock.held.get());
+}
@@ -0,0 +1,24 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(sync(as_name = "read_blocking"), async())]
+async fn read() -> u32 {
+    1
+}
+
+#[maybe_async_cfg::maybe(idents(Reader), sync(as_name = "BlockingReader"), async())]
+struct Reader(u32);
+
+#[maybe_async_cfg::maybe(sync(as_name = "BlockingReader"), async())]
+impl Reader {
+    async fn get(&self) -> u32 {
+        self.0
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(read_blocking(), 1);
+    assert_eq!(read_async().await, 1);
+    assert_eq!(BlockingReader(2).get(), 2);
+    assert_eq!(ReaderAsync(3).get().await, 3);
+}
@@ -0,0 +1,30 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(value(fn)), sync(), async())]
+async fn value() -> u32 {
+    1
+}
+
+// The sync version stays `async fn`, but calls `value_sync()` without awaiting it
+#[maybe_async_cfg::maybe(idents(value(fn), custom(fn)), sync(keep_asyncness), async())]
+async fn custom() -> u32 {
+    value().await + 1
+}
+
+#[maybe_async_cfg::maybe(idents(Counter), sync(keep_asyncness), async())]
+struct Counter(u32);
+
+#[maybe_async_cfg::maybe(idents(Counter, value(fn)), sync(keep_asyncness), async())]
+impl Counter {
+    async fn next(&self) -> u32 {
+        self.0 + value().await
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(custom_sync().await, 2);
+    assert_eq!(custom_async().await, 2);
+    assert_eq!(CounterSync(1).next().await, 2);
+    assert_eq!(CounterAsync(2).next().await, 3);
+}
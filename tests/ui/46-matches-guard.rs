@@ -0,0 +1,26 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(value(fn), limit(fn)), sync(), async())]
+async fn value(n: u32) -> Option<u32> {
+    Some(n)
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn), limit(fn)), sync(), async())]
+async fn limit() -> u32 {
+    3
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn), limit(fn), check(fn)), sync(), async())]
+async fn check(n: u32) -> (bool, bool, bool) {
+    (
+        matches!(value(n).await, Some(x) if x > 1),
+        matches!(value(n).await, Some(x) if x < limit().await),
+        matches!(value(n).await, Some(1) | Some(2),),
+    )
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(check_sync(2), (true, true, true));
+    assert_eq!(check_async(5).await, (true, false, false));
+}
@@ -0,0 +1,51 @@
+#![allow(dead_code)]
+
+// Stand-ins for an external async trait and its blocking counterpart
+mod nonblocking {
+    pub trait AsyncSource {
+        async fn read_byte(&mut self) -> u8;
+    }
+}
+
+mod blocking {
+    pub trait Source {
+        fn read_byte(&mut self) -> u8;
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Bytes), sync(), async())]
+struct Bytes(Vec<u8>);
+
+#[maybe_async_cfg::maybe(
+    idents(Bytes, nonblocking::AsyncSource(sync = "Source", async = "AsyncSource")),
+    sync(rename_crate(nonblocking = "blocking")),
+    async(),
+)]
+impl nonblocking::AsyncSource for Bytes {
+    async fn read_byte(&mut self) -> u8 {
+        self.0.pop().unwrap_or_default()
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(nonblocking::AsyncSource(sync = "Source", async = "AsyncSource"), first(fn)),
+    sync(rename_crate(nonblocking = "blocking")),
+    async(),
+)]
+async fn first<S: nonblocking::AsyncSource>(source: &mut S) -> u8 {
+    source.read_byte().await
+}
+
+#[async_std::main]
+async fn main() {
+    use blocking::Source;
+    use nonblocking::AsyncSource;
+
+    let mut bytes = BytesSync(vec![1, 2]);
+    assert_eq!(Source::read_byte(&mut bytes), 2);
+    assert_eq!(first_sync(&mut bytes), 1);
+
+    let mut bytes = BytesAsync(vec![3, 4]);
+    assert_eq!(AsyncSource::read_byte(&mut bytes).await, 4);
+    assert_eq!(first_async(&mut bytes).await, 3);
+}
@@ -0,0 +1,23 @@
+#![allow(dead_code)]
+
+use std::panic::Location;
+
+// `#[track_caller]` does not propagate through async state machines, so apply it in sync only
+#[maybe_async_cfg::maybe(sync(track_caller), async())]
+async fn caller_line() -> u32 {
+    Location::caller().line()
+}
+
+#[maybe_async_cfg::maybe(sync(inner(track_caller)), async())]
+async fn caller_line_inner() -> u32 {
+    Location::caller().line()
+}
+
+#[async_std::main]
+async fn main() {
+    let line = line!();
+    assert_eq!(caller_line_sync(), line + 1);
+    assert_eq!(caller_line_inner_sync(), line + 2);
+    assert_eq!(caller_line_async().await, 8);
+    assert_eq!(caller_line_inner_async().await, 13);
+}
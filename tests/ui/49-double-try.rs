@@ -0,0 +1,28 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(join(fn)), sync(), async())]
+async fn join(fail_outer: bool, fail_inner: bool) -> Result<Result<u32, String>, String> {
+    if fail_outer {
+        return Err("join".to_string());
+    }
+    if fail_inner {
+        return Ok(Err("task".to_string()));
+    }
+    Ok(Ok(7))
+}
+
+#[maybe_async_cfg::maybe(idents(join(fn), run(fn)), sync(), async())]
+async fn run(fail_outer: bool, fail_inner: bool) -> Result<u32, String> {
+    // like `tokio::spawn(...).await??`
+    let value = join(fail_outer, fail_inner).await??;
+    Ok(value)
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(run_sync(false, false), Ok(7));
+    assert_eq!(run_sync(true, false), Err("join".to_string()));
+    assert_eq!(run_sync(false, true), Err("task".to_string()));
+    assert_eq!(run_async(false, false).await, Ok(7));
+    assert_eq!(run_async(false, true).await, Err("task".to_string()));
+}
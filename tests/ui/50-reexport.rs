@@ -0,0 +1,28 @@
+#![allow(dead_code)]
+
+mod api {
+    #[maybe_async_cfg::maybe(sync(reexport), async())]
+    pub async fn read() -> u32 {
+        1
+    }
+
+    #[maybe_async_cfg::maybe(sync(reexport), async())]
+    pub struct Reader(pub u32);
+
+    // without `reexport` only the renamed item exists
+    #[maybe_async_cfg::maybe(sync(), async())]
+    pub async fn write() -> u32 {
+        2
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    // the canonical names refer to the sync versions
+    assert_eq!(api::read(), 1);
+    assert_eq!(api::read_sync(), 1);
+    assert_eq!(api::read_async().await, 1);
+    let api::Reader(value) = api::ReaderSync(3);
+    assert_eq!(value, 3);
+    assert_eq!(api::write_sync(), 2);
+}
@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(value(fn)), sync(), async())]
+async fn value() -> u32 {
+    2
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn), parens(fn)), sync(), async())]
+async fn parens() -> u32 {
+    (((value().await))) + ((value().await) * (value()).await)
+}
+
+// `$body` is passed to `maybe` as an invisible (none-delimited) group
+macro_rules! grouped {
+    ($name:ident, $body:expr) => {
+        #[maybe_async_cfg::maybe(idents(value(fn), $name(fn)), sync(), async())]
+        async fn $name() -> u32 {
+            $body * 2
+        }
+    };
+}
+
+grouped!(group, value().await + 1);
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(parens_sync(), 6);
+    assert_eq!(parens_async().await, 6);
+    assert_eq!(group_sync(), 6);
+    assert_eq!(group_async().await, 6);
+}
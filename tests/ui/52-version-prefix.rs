@@ -0,0 +1,22 @@
+#![allow(dead_code)]
+
+use maybe_async_cfg as mac;
+
+// The sync version re-emits `#[mac::maybe(...)]`, the async one `#[maybe_async_cfg::maybe(...)]`
+#[maybe_async_cfg::maybe(idents(value(fn)), sync(prefix = "mac"), async())]
+async fn value() -> u32 {
+    1
+}
+
+// A version inherits the prefix of the parent unless it overrides it
+#[mac::maybe(prefix = "mac", idents(value(fn), total(fn)), sync(), async(prefix = "maybe_async_cfg"))]
+async fn total() -> u32 {
+    value().await + 1
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(value_sync(), 1);
+    assert_eq!(total_sync(), 2);
+    assert_eq!(total_async().await, 2);
+}
@@ -0,0 +1,24 @@
+#![allow(dead_code)]
+
+// `pin_project` is not available here: the sync version compiles only without its attributes
+#[maybe_async_cfg::maybe(idents(Timed), sync())]
+#[pin_project::pin_project]
+struct Timed<F> {
+    #[pin]
+    inner: F,
+    elapsed: u32,
+}
+
+#[maybe_async_cfg::maybe(idents(State), sync())]
+#[pin_project(project = StateProj)]
+enum State<F> {
+    Running(#[pin] F),
+    Done,
+}
+
+fn main() {
+    let timed = TimedSync { inner: 1, elapsed: 2 };
+    assert_eq!(timed.inner + timed.elapsed, 3);
+    assert!(matches!(StateSync::Running(1), StateSync::Running(1)));
+    let _ = StateSync::<u32>::Done;
+}
@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(Request, Client), sync(), async())]
+struct Request<T>(T);
+
+#[maybe_async_cfg::maybe(idents(Request, Client), sync(), async())]
+impl<T: Default> Request<T> {
+    async fn send(self) -> T {
+        self.0
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Request, Client), sync(), async())]
+struct Client;
+
+#[maybe_async_cfg::maybe(idents(Request, Client), sync(), async())]
+impl Client {
+    fn request<T: Default>(&self) -> Request<T> {
+        Request(T::default())
+    }
+
+    // Method calls on the results need the turbofish to infer `T`
+    async fn fetch(&self) -> (u32, usize) {
+        let ones = self.request::<u8>().send().await.count_ones();
+        let len = self.request::<String>().send().await.len();
+        (ones, len)
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(ClientSync.fetch(), (0, 0));
+    assert_eq!(ClientAsync.fetch().await, (0, 0));
+}
@@ -0,0 +1,21 @@
+#![allow(dead_code)]
+
+maybe_async_cfg::define!(quiet, disable);
+
+// The struct has no async code, the renames still apply in both versions
+#[maybe_async_cfg::maybe(use = "quiet", force, idents(Config, Limits), sync(), async())]
+struct Config {
+    limits: Limits,
+}
+
+#[maybe_async_cfg::maybe(idents(Limits), sync(), async())]
+#[derive(Default)]
+struct Limits {
+    max: usize,
+}
+
+fn main() {
+    let sync = ConfigSync { limits: LimitsSync::default() };
+    let async_ = ConfigAsync { limits: LimitsAsync { max: 1 } };
+    assert_eq!(sync.limits.max + async_.limits.max, 1);
+}
@@ -0,0 +1,30 @@
+#![allow(dead_code)]
+
+// Stands in for `async_stream::stream!`: returns the body as it was converted
+macro_rules! stream {
+    ($($body:tt)*) => {
+        stringify!($($body)*)
+    };
+}
+
+#[maybe_async_cfg::maybe(idents(next_page(fn), pages(fn)), sync(), async())]
+fn pages() -> &'static str {
+    stream! {
+        let mut page = next_page().await;
+        while let Some(value) = page {
+            yield value;
+            page = next_page().await;
+        }
+    }
+}
+
+fn main() {
+    let sync = pages_sync();
+    assert!(!sync.contains("await"));
+    assert!(sync.contains("next_page_sync()"));
+    assert!(sync.contains("yield value"));
+
+    let async_ = pages_async();
+    assert!(async_.contains("next_page_async().await"));
+    assert!(async_.contains("yield value"));
+}
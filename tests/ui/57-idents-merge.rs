@@ -0,0 +1,26 @@
+#![allow(dead_code)]
+
+struct Blocking;
+
+#[maybe_async_cfg::maybe(idents(Conn), sync(), async())]
+struct Conn;
+
+#[maybe_async_cfg::maybe(idents(Pool), sync(), async())]
+struct Pool;
+
+// Both `idents` groups apply, `Pool` of the sync version overrides the inherited one
+#[maybe_async_cfg::maybe(
+    idents(Conn, Client),
+    idents(Pool),
+    sync(idents(Pool(sync = "Blocking"))),
+    async(),
+)]
+struct Client {
+    conn: Conn,
+    pool: Pool,
+}
+
+fn main() {
+    let _ = ClientSync { conn: ConnSync, pool: Blocking };
+    let _ = ClientAsync { conn: ConnAsync, pool: PoolAsync };
+}
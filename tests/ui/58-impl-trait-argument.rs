@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(Service), sync(), async())]
+trait Service {
+    async fn call(&self, request: u32) -> u32;
+}
+
+struct Double;
+
+#[maybe_async_cfg::maybe(idents(Service), keep_self, sync(), async())]
+impl Service for Double {
+    async fn call(&self, request: u32) -> u32 {
+        request * 2
+    }
+}
+
+// The `impl Trait` argument is kept (renamed), the call on it loses its `.await`
+#[maybe_async_cfg::maybe(idents(Service, run(fn)), sync(), async())]
+async fn run(svc: impl Service, requests: impl IntoIterator<Item = u32>) -> u32 {
+    let mut total = 0;
+    for request in requests {
+        total += svc.call(request).await;
+    }
+    total
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(run_sync(Double, [1, 2]), 6);
+    assert_eq!(run_async(Double, vec![3]).await, 6);
+}
@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+struct BlockingClient;
+struct AsyncClient;
+
+impl BlockingClient {
+    fn connect() -> u32 {
+        1
+    }
+}
+
+impl AsyncClient {
+    async fn connect() -> u32 {
+        2
+    }
+}
+
+// The aliased types are unrelated, one alias per version is declared by hand
+type ClientSync = BlockingClient;
+type ClientAsync = AsyncClient;
+
+#[maybe_async_cfg::maybe(idents(Client, total(fn)), sync(), async())]
+async fn total() -> u32 {
+    Client::connect().await + <Client>::connect().await
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(total_sync(), 2);
+    assert_eq!(total_async().await, 4);
+}
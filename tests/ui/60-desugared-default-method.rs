@@ -0,0 +1,29 @@
+#![allow(dead_code)]
+
+use std::future::Future;
+
+#[maybe_async_cfg::maybe(idents(Counter), sync(), async())]
+trait Counter {
+    async fn bar(&self) -> u32;
+
+    // Hand-desugared `async fn`: in the sync version this is `fn foo(&self) -> u32 { self.bar() + 1 }`
+    fn foo(&self) -> impl Future<Output = u32> + '_ {
+        async move { self.bar().await + 1 }
+    }
+}
+
+struct One;
+
+#[maybe_async_cfg::maybe(idents(Counter), keep_self, sync(), async())]
+impl Counter for One {
+    async fn bar(&self) -> u32 {
+        1
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    let sync: u32 = CounterSync::foo(&One);
+    assert_eq!(sync, 2);
+    assert_eq!(CounterAsync::foo(&One).await, 2);
+}
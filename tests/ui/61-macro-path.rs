@@ -0,0 +1,38 @@
+#![allow(dead_code)]
+
+// The macros are re-exported at a deeper path, the crate itself is not named anywhere below
+mod macros {
+    pub use maybe_async_cfg::*;
+}
+
+#[crate::macros::maybe(
+    macro_path = "crate::macros::maybe",
+    idents(Answer),
+    sync(key = "sync"),
+    async(key = "async"),
+)]
+struct Answer;
+
+#[crate::macros::maybe(
+    macro_path = "crate::macros::maybe",
+    idents(Answer),
+    sync(key = "sync"),
+    async(key = "async"),
+)]
+impl Answer {
+    #[crate::macros::only_if(key = "sync")]
+    fn get(&self) -> u32 {
+        1
+    }
+
+    #[crate::macros::only_if(key = "async")]
+    async fn get(&self) -> u32 {
+        2
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(AnswerSync.get(), 1);
+    assert_eq!(AnswerAsync.get().await, 2);
+}
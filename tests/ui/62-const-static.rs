@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(Client), sync(), async())]
+struct Client {
+    retries: u32,
+}
+
+#[maybe_async_cfg::maybe(idents(Client), sync(), async())]
+const CLIENT: Client = Client { retries: 3 };
+
+#[maybe_async_cfg::maybe(idents(Client), sync(), async())]
+static DEFAULT: [Client; 1] = [Client { retries: 1 }];
+
+#[maybe_async_cfg::maybe(
+    idents(Client, RETRIES(sync = "BLOCKING_RETRIES", async = "RETRIES")),
+    sync(),
+    async(),
+)]
+const RETRIES: u32 = 2;
+
+fn main() {
+    let sync: &ClientSync = &CLIENT_SYNC;
+    let async_: &ClientAsync = &CLIENT_ASYNC;
+    assert_eq!(sync.retries + async_.retries, 6);
+    assert_eq!(DEFAULT_SYNC[0].retries + DEFAULT_ASYNC[0].retries, 2);
+    assert_eq!(BLOCKING_RETRIES + RETRIES, 4);
+}
@@ -0,0 +1,21 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(Stream), sync(), async())]
+struct Stream {
+    id: u32,
+}
+
+#[maybe_async_cfg::maybe(idents(Stream), sync(), async())]
+type Conn = Option<Stream>;
+
+#[maybe_async_cfg::maybe(idents(Stream, Conn, open(fn)), sync(), async())]
+async fn open(id: u32) -> Conn {
+    Some(Stream { id })
+}
+
+#[async_std::main]
+async fn main() {
+    let sync: ConnSync = open_sync(1);
+    let async_: Option<StreamAsync> = open_async(2).await;
+    assert_eq!(sync.unwrap().id + async_.unwrap().id, 3);
+}
@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(fetch(fn)), sync(), async())]
+async fn fetch(x: u32) -> u32 {
+    x
+}
+
+// Only the macro is renamed, its body is kept as is
+#[maybe_async_cfg::maybe(sync(), async())]
+macro_rules! double {
+    ($x:expr) => {
+        $x * 2
+    };
+}
+
+// The body is converted: `fetch` is renamed, `.await` is removed in the sync version
+#[maybe_async_cfg::maybe(idents(fetch(fn)), rewrite_macro_body, sync(), async())]
+macro_rules! fetch_sum(
+    ($($x:expr),*) => {
+        0 $(+ fetch($x).await)*
+    };
+);
+
+#[maybe_async_cfg::maybe(idents(fetch_sum(fn), double(fn), total(fn)), sync(), async())]
+async fn total() -> u32 {
+    let sum = fetch_sum!(1, 2);
+    double!(sum)
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(total_sync(), 6);
+    assert_eq!(total_async().await, 6);
+}
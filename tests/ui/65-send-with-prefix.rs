@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+extern crate maybe_async_cfg as mac;
+
+use std::rc::Rc;
+
+// Both the inherited `prefix` and `send` must survive re-emitting the version attribute
+#[mac::maybe(prefix = "mac", idents(Store), sync(), async("async_trait::async_trait(?Send)"))]
+trait Store {
+    async fn get(&self) -> u32;
+}
+
+struct Local;
+
+#[mac::maybe(prefix = "mac", idents(Store), keep_self, sync(), async(send = "?Send"))]
+impl Store for Local {
+    async fn get(&self) -> u32 {
+        // `Rc` is not `Send`, the future is fine only with `async_trait(?Send)`
+        let value = Rc::new(2);
+        async {}.await;
+        *value
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(StoreSync::get(&Local), 2);
+    // `dyn` needs the `async_trait` attribute added for `send`
+    let store: Box<dyn StoreAsync> = Box::new(Local);
+    assert_eq!(store.get().await, 2);
+}
@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+
+struct Marker;
+
+mod blocking {
+    pub struct Reader<T>(pub T);
+
+    impl Reader<u32> {
+        pub fn read(&self) -> u32 {
+            self.0
+        }
+    }
+}
+
+mod nonblocking {
+    pub struct Reader<T>(pub T);
+    pub struct Marker;
+
+    impl Reader<u32> {
+        pub async fn read(&self) -> u32 {
+            self.0 * 10
+        }
+    }
+}
+
+// `Marker` has no replacement in the sync version and is kept there
+#[maybe_async_cfg::maybe(
+    replace_ident("Reader", sync = "blocking::Reader", async = "nonblocking::Reader"),
+    replace_ident("Marker", async = "nonblocking::Marker"),
+    idents(read_all(fn)),
+    sync(),
+    async(),
+)]
+async fn read_all(value: u32) -> (u32, Marker) {
+    let reader: Reader<u32> = Reader::<u32>(value);
+    (reader.read().await, Marker)
+}
+
+#[async_std::main]
+async fn main() {
+    let (sync, Marker) = read_all_sync(1);
+    let (async_, nonblocking::Marker) = read_all_async(1).await;
+    assert_eq!(sync + async_, 11);
+}
@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+
+mod nonblocking {
+    pub struct Mutex<T>(std::sync::Mutex<T>);
+
+    impl<T: Copy> Mutex<T> {
+        pub fn new(value: T) -> Self {
+            Self(std::sync::Mutex::new(value))
+        }
+
+        pub async fn get(&self) -> T {
+            *self.0.lock().unwrap()
+        }
+    }
+}
+
+mod blocking {
+    pub struct Mutex<T>(pub std::sync::Mutex<T>);
+
+    impl<T: Copy> Mutex<T> {
+        pub fn new(value: T) -> Self {
+            Self(std::sync::Mutex::new(value))
+        }
+
+        pub fn get(&self) -> T {
+            *self.0.lock().unwrap()
+        }
+    }
+}
+
+// `crate::`, `self::` and the leading `::` are ignored when matching, generic arguments are kept
+#[maybe_async_cfg::maybe(
+    replace_path("crate::blocking::Mutex", async = "nonblocking::Mutex"),
+    replace_path("::std::sync::Mutex", async = "nonblocking::Mutex"),
+    idents(Counter),
+    sync(),
+    async(),
+)]
+struct Counter {
+    value: crate::blocking::Mutex<u32>,
+    other: ::std::sync::Mutex<u32>,
+}
+
+#[maybe_async_cfg::maybe(
+    replace_path("crate::blocking::Mutex", async = "nonblocking::Mutex"),
+    replace_path("::std::sync::Mutex", async = "nonblocking::Mutex"),
+    idents(Counter),
+    sync(),
+    async(),
+)]
+impl Counter {
+    fn new(value: u32) -> Self {
+        Self {
+            value: self::blocking::Mutex::<u32>::new(value),
+            other: std::sync::Mutex::new(0),
+        }
+    }
+
+    async fn get(&self) -> u32 {
+        self.value.get().await
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    let _: &blocking::Mutex<u32> = &CounterSync::new(1).value;
+    let _: &nonblocking::Mutex<u32> = &CounterAsync::new(1).value;
+    let _: &std::sync::Mutex<u32> = &CounterSync::new(1).other;
+    let _: &nonblocking::Mutex<u32> = &CounterAsync::new(1).other;
+    assert_eq!(CounterSync::new(1).get(), 1);
+    assert_eq!(CounterAsync::new(2).get().await, 2);
+}
@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+// any use of `async_trait::async_trait` in this crate fails to resolve
+mod async_trait {}
+
+#[maybe_async_cfg::maybe(idents(Store), async_trait = "native", sync(), async(send = "Send"))]
+trait Store {
+    async fn get(&self) -> u32;
+
+    async fn twice(&self) -> u32 {
+        self.get().await * 2
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Local), async_trait = "native", sync(), async())]
+trait Local {
+    async fn get(&self) -> u32;
+}
+
+struct Memory;
+
+#[maybe_async_cfg::maybe(idents(Store), keep_self, async_trait = "native", sync(), async(send = "Send"))]
+impl Store for Memory {
+    async fn get(&self) -> u32 {
+        2
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Local), keep_self, async_trait = "native", sync(), async())]
+impl Local for Memory {
+    async fn get(&self) -> u32 {
+        // `Rc` is not `Send`, this is fine without `send`
+        let value = std::rc::Rc::new(3);
+        std::future::ready(()).await;
+        *value
+    }
+}
+
+fn assert_send<F: Send>(future: F) -> F {
+    future
+}
+
+// the futures of any implementation are `Send`
+async fn generic<S: StoreAsync + Sync>(store: &S) -> u32 {
+    assert_send(store.twice()).await
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(StoreSync::twice(&Memory), 4);
+    assert_eq!(generic(&Memory).await, 4);
+    assert_eq!(LocalSync::get(&Memory), 3);
+    assert_eq!(LocalAsync::get(&Memory).await, 3);
+}
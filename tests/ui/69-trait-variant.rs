@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+use std::future::Future;
+use std::rc::Rc;
+
+// async version: `#[trait_variant::make(Send)] trait StoreAsync`
+#[maybe_async_cfg::maybe(idents(Store), async_trait = "trait_variant", sync(), async(send = "Send"))]
+trait Store {
+    async fn get(&self) -> u32;
+}
+
+// async version: `#[trait_variant::make()] trait LocalAsync`, futures don't have to be `Send`
+#[maybe_async_cfg::maybe(idents(Local), async_trait = "trait_variant", sync(), async(send = "?Send"))]
+trait Local {
+    async fn get(&self) -> u32;
+}
+
+struct Memory;
+
+impl StoreSync for Memory {
+    fn get(&self) -> u32 {
+        1
+    }
+}
+
+impl StoreAsync for Memory {
+    async fn get(&self) -> u32 {
+        2
+    }
+}
+
+impl LocalAsync for Memory {
+    async fn get(&self) -> u32 {
+        let value = Rc::new(3);
+        async {}.await;
+        *value
+    }
+}
+
+fn assert_send<F: Future + Send>(future: F) -> F {
+    future
+}
+
+// compiles only if the trait requires `Send` futures
+async fn get_send<S: StoreAsync>(store: &S) -> u32 {
+    assert_send(store.get()).await
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(StoreSync::get(&Memory), 1);
+    assert_eq!(get_send(&Memory).await, 2);
+    assert_eq!(LocalAsync::get(&Memory).await, 3);
+}
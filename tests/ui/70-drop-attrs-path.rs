@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(
+    idents(check(fn)),
+    sync(drop_attrs(tokio::test)),
+    async(),
+)]
+#[tokio::test]
+async fn check() {
+    assert_eq!(async { 1 }.await, 1);
+}
+
+#[maybe_async_cfg::maybe(
+    idents(check_all(fn)),
+    sync(drop_attrs("tokio::*", "async_std::test")),
+    async(),
+)]
+#[tokio::main]
+async fn check_all() {
+    assert_eq!(async { 2 }.await, 2);
+}
+
+fn main() {
+    check_sync();
+    check_all_sync();
+    check_all_async();
+}
@@ -0,0 +1,41 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(
+    idents(check(fn)),
+    add_attrs(sync(inline), async("tokio::main(flavor = \"current_thread\")")),
+    sync(),
+    async(),
+)]
+async fn check() {
+    assert_eq!(async { 1 }.await, 1);
+}
+
+struct CounterSync;
+struct CounterAsync;
+
+#[maybe_async_cfg::maybe(
+    idents(Counter),
+    add_attrs(sync("allow(clippy::unused_self)")),
+    sync(add_attrs(async(must_use))),
+    async(add_attrs(async(must_use))),
+)]
+impl Counter {
+    async fn get(&self) -> u32 {
+        1
+    }
+}
+
+#[maybe_async_cfg::maybe(add_attrs(sync(derive(Clone)), async(derive(Debug))), sync(), async())]
+struct Config {
+    retries: u32,
+}
+
+fn main() {
+    let config = ConfigSync { retries: 3 };
+    assert_eq!(config.clone().retries, 3);
+    assert_eq!(format!("{:?}", ConfigAsync { retries: 3 }), "ConfigAsync { retries: 3 }");
+
+    check_sync();
+    // `tokio::main` makes the async version a sync fn
+    check_async();
+}
@@ -0,0 +1,37 @@
+#![allow(dead_code)]
+
+macro_rules! pair {
+    ($a:expr, $b:expr) => {
+        ($a, $b)
+    };
+}
+
+// only the sync version is generated, macro bodies are not renamed
+fn value() -> u32 {
+    1
+}
+
+// `strip_macro_awaits`: `.await` is removed from the bodies of `vec!` and `pair!`, nested too
+#[maybe_async_cfg::maybe(idents(collect(fn)), strip_macro_awaits, sync())]
+async fn collect() -> Vec<u32> {
+    let x = value().await;
+    assert!(matches!(value().await, 1));
+    let nested = vec![pair!(value().await, vec![value().await]).0];
+    assert_eq!(format!("{}", ".await"), ".await");
+    let mut values = vec![value().await, x];
+    values.extend(nested);
+    values
+}
+
+// without it, bodies of unknown macros are left as is; `matches!` is converted anyway
+#[maybe_async_cfg::maybe(idents(check(fn)), sync())]
+async fn check() -> &'static str {
+    assert!(matches!(value().await, 1));
+    assert!(matches!(value().await, _));
+    stringify!(value().await)
+}
+
+fn main() {
+    assert_eq!(collect_sync(), vec![1, 1, 1]);
+    assert_eq!(check_sync().replace(' ', ""), "value().await");
+}
@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+async fn fetch() -> u32 {
+    1
+}
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(future)
+}
+
+#[maybe_async_cfg::maybe(idents(load(fn)), sync(), async())]
+async fn load() -> u32 {
+    // the sync version runs the async code on its own
+    block_on(maybe_async_cfg::keep!(async { fetch().await + fetch().await }))
+}
+
+#[maybe_async_cfg::maybe(idents(load(fn), total(fn)), sync(), async())]
+async fn total() -> u32 {
+    let mut sum = load().await;
+    maybe_async_cfg::keep!(sum += block_on(async { fetch().await }));
+    sum
+}
+
+fn main() {
+    assert_eq!(load_sync(), 2);
+    assert_eq!(total_sync(), 3);
+}
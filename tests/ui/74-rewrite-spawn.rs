@@ -0,0 +1,22 @@
+#![allow(dead_code)]
+
+use std::sync::mpsc::channel;
+
+#[maybe_async_cfg::maybe(idents(value(fn)), sync(), async())]
+async fn value() -> u32 {
+    1
+}
+
+#[maybe_async_cfg::maybe(idents(value(fn), compute(fn)), rewrite_spawn, sync(), async())]
+async fn compute() -> u32 {
+    let (tx, rx) = channel();
+    // sync version: `std::thread::spawn(move || { ... })`
+    tokio::task::spawn(async move {
+        tx.send(value().await + 1).unwrap();
+    });
+    rx.recv().unwrap()
+}
+
+fn main() {
+    assert_eq!(compute_sync(), 2);
+}
@@ -0,0 +1,26 @@
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+#[maybe_async_cfg::maybe(idents(pause(fn)), rewrite_sleep, sync(), async())]
+async fn pause() {
+    tokio::time::sleep(Duration::from_millis(1)).await;
+    async_std::task::sleep(Duration::from_millis(1)).await;
+}
+
+#[maybe_async_cfg::maybe(idents(wait(fn)), rewrite_sleep, sync(), async())]
+async fn wait() {
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(1);
+    tokio::time::sleep_until(deadline).await;
+}
+
+#[tokio::main]
+async fn main() {
+    pause_sync();
+    wait_sync();
+    // the sync version measures the time with the std clock, no runtime is needed
+    std::thread::spawn(wait_sync).join().unwrap();
+    // the async version still sleeps with tokio
+    pause_async().await;
+    wait_async().await;
+}
@@ -0,0 +1,24 @@
+#![allow(dead_code)]
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(future)
+}
+
+#[maybe_async_cfg::maybe(idents(fetch(fn)), sync(), async())]
+async fn fetch(x: u32) -> u32 {
+    x + 1
+}
+
+// only async code is available: the sync version is a blocking shim
+async fn connect() -> u32 {
+    async { 10 }.await
+}
+
+#[maybe_async_cfg::maybe(idents(fetch(fn), load(fn)), sync_via_block_on = "block_on", sync(), async())]
+async fn load(x: u32) -> u32 {
+    connect().await + fetch(x).await
+}
+
+fn main() {
+    assert_eq!(load_sync(1), 12);
+}
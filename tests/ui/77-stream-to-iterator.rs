@@ -0,0 +1,87 @@
+#![allow(dead_code)]
+
+use async_std::stream::{Stream, StreamExt};
+
+// a minimal `TryStream` with `try_next()` for the async version, also an `Iterator` for the sync one
+mod try_streams {
+    pub trait TryStream {
+        type Ok;
+        type Error;
+    }
+
+    pub struct Numbers(pub std::vec::IntoIter<Result<u32, String>>);
+
+    impl TryStream for Numbers {
+        type Ok = u32;
+        type Error = String;
+    }
+
+    impl Numbers {
+        pub async fn try_next(&mut self) -> Result<Option<u32>, String> {
+            self.0.next().transpose()
+        }
+    }
+
+    impl Iterator for Numbers {
+        type Item = Result<u32, String>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+}
+
+use try_streams::{Numbers, TryStream};
+
+#[maybe_async_cfg::maybe(
+    idents(numbers(fn)),
+    rewrite_stream,
+    replace_path("async_std::stream::from_iter", sync = "IntoIterator::into_iter"),
+    sync(),
+    async(),
+)]
+fn numbers() -> impl Stream<Item = u32> {
+    async_std::stream::from_iter(vec![1, 2, 3])
+}
+
+#[maybe_async_cfg::maybe(idents(numbers(fn), sum(fn)), rewrite_stream, sync(), async())]
+async fn sum() -> u32 {
+    let mut stream = numbers();
+    let mut total = 0;
+    while let Some(x) = stream.next().await {
+        total += x;
+    }
+    total
+}
+
+#[maybe_async_cfg::maybe(idents(checked(fn)), rewrite_stream, sync(), async())]
+fn checked() -> impl TryStream<Ok = u32, Error = String> {
+    Numbers(vec![Ok(1), Ok(2), Err("overflow".to_string())].into_iter())
+}
+
+#[maybe_async_cfg::maybe(idents(checked(fn), checked_sum(fn)), rewrite_stream, sync(), async())]
+async fn checked_sum() -> Result<u32, String> {
+    let mut stream = Numbers(vec![Ok(1), Ok(2), Err("overflow".to_string())].into_iter());
+    let mut total = 0;
+    while let Some(x) = stream.try_next().await? {
+        total += x;
+    }
+    Ok(total)
+}
+
+// not applied with `send = "?Send"`: the sync version still returns a `Stream`
+#[maybe_async_cfg::maybe(idents(local_numbers(fn)), rewrite_stream, sync(send = "?Send"))]
+fn local_numbers() -> impl Stream<Item = u32> {
+    async_std::stream::from_iter(vec![1, 2])
+}
+
+#[async_std::main]
+async fn main() {
+    let _: Vec<Result<u32, String>> = checked_sync().collect();
+    assert_eq!(sum_sync(), 6);
+    assert_eq!(sum_async().await, 6);
+    assert_eq!(checked_sum_sync(), Err("overflow".to_string()));
+    assert_eq!(checked_sum_async().await, Err("overflow".to_string()));
+    let mut local = local_numbers_sync();
+    assert_eq!(local.next().await, Some(1));
+}
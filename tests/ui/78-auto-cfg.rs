@@ -0,0 +1,37 @@
+#![allow(dead_code)]
+
+// `test_sync` is never enabled: the sync version is not compiled
+#[maybe_async_cfg::maybe(
+    idents(value(fn)),
+    auto_cfg(sync = "test_sync", async = "test_async"),
+    sync(),
+    async(),
+)]
+async fn value() -> u32 {
+    1
+}
+
+fn value_sync() -> u32 {
+    2
+}
+
+// explicit conditions are combined with `all(...)`
+#[maybe_async_cfg::maybe(
+    idents(other(fn)),
+    auto_cfg(sync = "test_sync"),
+    sync(not(feature = "test_other")),
+    async(not(feature = "test_other")),
+)]
+async fn other() -> u32 {
+    3
+}
+
+fn other_sync() -> u32 {
+    4
+}
+
+fn main() {
+    assert_eq!(value_sync(), 2);
+    assert_eq!(other_sync(), 4);
+    let _ = other_async();
+}
@@ -0,0 +1,19 @@
+#![allow(dead_code, unexpected_cfgs)]
+
+// `never` is not enabled: the fields exist only where its condition is replaced
+#[maybe_async_cfg::maybe(
+    idents(Config),
+    sync(replace_feature("never", not(feature = "never"))),
+    async(replace_feature("never", "")),
+)]
+struct Config {
+    #[cfg(feature = "never")]
+    value: u32,
+    #[cfg(all(feature = "never", not(feature = "other")))]
+    other: u32,
+}
+
+fn main() {
+    let _ = ConfigSync { value: 1, other: 2 };
+    let _ = ConfigAsync { value: 1, other: 2 };
+}
@@ -0,0 +1,39 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(limit(fn)), sync(), async())]
+const fn limit() -> u32 {
+    10
+}
+
+#[maybe_async_cfg::maybe(idents(fetch(fn)), sync(), async())]
+async fn fetch() -> u32 {
+    1
+}
+
+#[maybe_async_cfg::maybe(idents(Source, limit(fn), fetch(fn)), sync(), async())]
+trait Source {
+    const LIMIT: u32 = limit();
+
+    async fn get(&self) -> u32;
+
+    async fn total(&self) -> u32 {
+        let total = self.get().await + fetch().await;
+        total.min(Self::LIMIT)
+    }
+}
+
+struct Memory;
+
+#[maybe_async_cfg::maybe(idents(Source), keep_self, sync(), async())]
+impl Source for Memory {
+    async fn get(&self) -> u32 {
+        2
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    assert_eq!(<Memory as SourceSync>::LIMIT, 10);
+    assert_eq!(SourceSync::total(&Memory), 3);
+    assert_eq!(SourceAsync::total(&Memory).await, 3);
+}
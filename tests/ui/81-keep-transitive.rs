@@ -0,0 +1,36 @@
+#![allow(dead_code)]
+
+// only one version is generated and it keeps its name
+#[maybe_async_cfg::maybe(idents(Shared(keep)), sync())]
+struct Shared {
+    value: u32,
+}
+
+#[maybe_async_cfg::maybe(idents(Shared(keep)), sync())]
+impl Shared {
+    fn new(value: u32) -> Shared {
+        Self { value }
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(Shared(keep), Holder), sync(), async())]
+struct Holder {
+    shared: Shared,
+    list: Vec<Shared>,
+}
+
+#[maybe_async_cfg::maybe(idents(Shared(keep), Holder), sync(), async())]
+impl Holder {
+    async fn new(value: u32) -> Holder {
+        let shared: Shared = Shared::new(value);
+        Holder { shared, list: vec![Shared::new(value)] }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let holder: HolderSync = HolderSync::new(1);
+    assert_eq!(holder.shared.value, 1);
+    let holder: HolderAsync = HolderAsync::new(2).await;
+    assert_eq!(holder.list[0].value, 2);
+}
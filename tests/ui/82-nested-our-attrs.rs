@@ -0,0 +1,26 @@
+#![allow(dead_code)]
+
+// attributes of the crate left on fields and arguments are consumed by the conversion
+#[maybe_async_cfg::maybe(idents(Client), sync(), async())]
+struct Client {
+    #[maybe_async_cfg::maybe(sync())]
+    timeout: u32,
+}
+
+#[maybe_async_cfg::maybe(idents(Client), sync(), async())]
+impl Client {
+    async fn new(#[maybe_async_cfg::noop] timeout: u32) -> Self {
+        Self { timeout }
+    }
+}
+
+#[maybe_async_cfg::maybe(idents(State), sync(), async())]
+enum State {
+    #[maybe_async_cfg::maybe(async())]
+    Idle,
+}
+
+fn main() {
+    assert_eq!(ClientSync::new(1).timeout, 1);
+    let _ = StateSync::Idle;
+}
@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+use std::rc::Rc;
+
+// runs the future (or value) in place, no `Send` required
+mod local {
+    pub fn spawn<T>(task: T) -> T {
+        task
+    }
+}
+
+// under `?Send` the spawn call is not turned into `std::thread::spawn`
+#[maybe_async_cfg::maybe(
+    idents(count(fn)),
+    rewrite_spawn,
+    sync(send = "?Send"),
+    async(send = "?Send"),
+)]
+async fn count() -> usize {
+    let shared = Rc::new(1);
+    let task = local::spawn(async move { Rc::strong_count(&shared) });
+    task.await
+}
+
+fn main() {
+    assert_eq!(count_sync(), 1);
+}
@@ -0,0 +1,30 @@
+#![allow(dead_code, unexpected_cfgs)]
+
+// `never` is not enabled, the versions exist only if their conditions are replaced
+#[maybe_async_cfg::maybe(
+    idents(Config),
+    replace_feature("never", not(feature = "never")),
+    sync(feature = "never"),
+    async(cfg(all(feature = "never", not(feature = "other")))),
+)]
+struct Config {
+    value: u32,
+}
+
+// the replacement of the version wins over the common one
+#[maybe_async_cfg::maybe(
+    idents(Limits),
+    replace_feature("never", "other"),
+    sync(feature = "never", replace_feature("never", not(feature = "never"))),
+    async(),
+)]
+struct Limits {
+    value: u32,
+}
+
+fn main() {
+    let _ = LimitsSync { value: 3 };
+    let _ = LimitsAsync { value: 4 };
+    let _ = ConfigSync { value: 1 };
+    let _ = ConfigAsync { value: 2 };
+}
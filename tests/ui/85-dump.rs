@@ -0,0 +1,25 @@
+#![allow(dead_code)]
+
+// the generated code is reported as a warning and written to `MAYBE_ASYNC_CFG_DUMP_DIR` (set by
+// tests/test.rs) while compiling, one file per version
+#[maybe_async_cfg::maybe(idents(fetch(fn)), dump, sync(), async())]
+async fn fetch() -> u32 {
+    1
+}
+
+fn dumped(prefix: &str) -> String {
+    let dir = std::env::var("MAYBE_ASYNC_CFG_DUMP_DIR").unwrap();
+    let entry = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap())
+        .find(|entry| entry.file_name().to_string_lossy().starts_with(prefix))
+        .unwrap();
+    std::fs::read_to_string(entry.path()).unwrap()
+}
+
+fn main() {
+    assert_eq!(fetch_sync(), 1);
+
+    assert!(dumped("fetch_sync.sync.").contains("fn fetch_sync"));
+    assert!(dumped("fetch_async.async.").contains("async fn fetch_async"));
+}
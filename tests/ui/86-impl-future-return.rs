@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+use std::{future::Future, pin::Pin};
+
+#[maybe_async_cfg::maybe(idents(read(fn)), sync(), async())]
+fn read(text: &str) -> impl Future<Output = Result<u32, String>> + '_ {
+    async move { text.parse::<u32>().map_err(|err| err.to_string()) }
+}
+
+#[maybe_async_cfg::maybe(rewrite_boxed, idents(read(fn), read_boxed(fn)), sync(), async())]
+fn read_boxed(text: &str) -> Pin<Box<dyn Future<Output = Result<u32, String>> + '_>> {
+    Box::pin(async move {
+        let value = read(text).await?;
+        Ok(value * 2)
+    })
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(read_sync("1"), Ok(1));
+    assert!(read_sync("x").is_err());
+    assert_eq!(read_boxed_sync("2"), Ok(4));
+
+    assert_eq!(read_async("1").await, Ok(1));
+    assert_eq!(read_boxed_async("2").await, Ok(4));
+    assert!(read_boxed_async("x").await.is_err());
+}
@@ -0,0 +1,53 @@
+#![allow(dead_code)]
+
+use std::{future::Future, pin::Pin};
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+// The same as `futures::FutureExt::boxed`
+trait FutureExt: Future {
+    fn boxed<'a>(self) -> BoxFuture<'a, Self::Output>
+    where
+        Self: Sized + Send + 'a,
+    {
+        Box::pin(self)
+    }
+}
+
+impl<F: Future> FutureExt for F {}
+
+#[maybe_async_cfg::maybe(idents(base(fn)), sync(), async())]
+async fn base() -> u32 {
+    1
+}
+
+// `.boxed()` is removed, the await inside the async block is still removed
+#[maybe_async_cfg::maybe(rewrite_boxed, idents(base(fn), double(fn)), sync(), async())]
+fn double() -> BoxFuture<'static, u32> {
+    async move { base().await * 2 }.boxed()
+}
+
+// `Box::pin(fut)` is removed for any future, not only for an async block
+#[maybe_async_cfg::maybe(rewrite_boxed, idents(base(fn), pinned(fn)), sync(), async())]
+fn pinned() -> BoxFuture<'static, u32> {
+    Box::pin(base())
+}
+
+// without `rewrite_boxed` the sync version still returns the boxed future, only the awaits inside
+// the block are removed
+#[maybe_async_cfg::maybe(idents(base(fn), tripled(fn)), sync(), async())]
+fn tripled() -> BoxFuture<'static, u32> {
+    Box::pin(async move { base().await * 3 })
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(double_sync(), 2);
+    assert_eq!(pinned_sync(), 1);
+    let boxed: BoxFuture<'static, u32> = tripled_sync();
+    assert_eq!(async_std::task::block_on(boxed), 3);
+    assert_eq!(tripled_async().await, 3);
+
+    assert_eq!(double_async().await, 2);
+    assert_eq!(pinned_async().await, 1);
+}
@@ -0,0 +1,30 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(increment(fn)), sync(), async())]
+async fn increment(value: u32) -> u32 {
+    value + 1
+}
+
+#[maybe_async_cfg::maybe(idents(increment(fn), combine(fn)), rewrite_boxed, sync(), async())]
+async fn combine() -> u32 {
+    // `async` is removed from the closure
+    let first = async move |value: u32| increment(value).await * 2;
+    // with `rewrite_boxed` the async block returned by the closure is flattened
+    let second = move || async move { increment(10).await };
+    first(1).await + second().await
+}
+
+// without it only `async` of the closure itself is removed: the closure of the sync version still
+// returns a future, the awaits inside it are removed
+#[maybe_async_cfg::maybe(idents(increment(fn), deferred(fn)), sync())]
+async fn deferred() -> u32 {
+    let make = move || async move { increment(20).await };
+    async_std::task::block_on(make())
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(combine_sync(), 15);
+    assert_eq!(combine_async().await, 15);
+    assert_eq!(deferred_sync(), 21);
+}
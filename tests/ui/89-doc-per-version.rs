@@ -0,0 +1,19 @@
+//! Each version gets its own doc comment.
+#![deny(missing_docs)]
+
+/// Documented versions.
+pub mod versions {
+    #[maybe_async_cfg::maybe(
+        sync(inner(doc = "Blocking version.")),
+        async(inner(doc = "Async version."))
+    )]
+    pub async fn documented() -> u32 {
+        1
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(versions::documented_sync(), 1);
+    assert_eq!(versions::documented_async().await, 1);
+}
@@ -0,0 +1,24 @@
+#![allow(dead_code)]
+
+mod inner {
+    // Only the sync version is visible outside of the module
+    #[maybe_async_cfg::maybe(vis(sync = "pub(crate)"), idents(compute(fn)), sync(), async())]
+    async fn compute() -> u32 {
+        1
+    }
+
+    #[maybe_async_cfg::maybe(vis(sync = "pub(crate)", async = ""), idents(Counter), sync(), async())]
+    pub struct Counter(pub u32);
+
+    #[maybe_async_cfg::maybe(idents(compute(fn)), sync(), async())]
+    pub async fn check() -> u32 {
+        compute().await + CounterAsync(1).0
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(inner::compute_sync(), 1);
+    assert_eq!(inner::CounterSync(2).0, 2);
+    assert_eq!(inner::check_async().await, 2);
+}
@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+macro_rules! my_try {
+    ($expr:expr, $context:expr) => {
+        match $expr {
+            Ok(value) => value,
+            Err(err) => return Err(format!("{}: {}", $context, err)),
+        }
+    };
+}
+
+#[maybe_async_cfg::maybe(idents(fetch(fn)), sync(), async())]
+async fn fetch(fail: bool) -> Result<u32, String> {
+    if fail {
+        Err("failed".to_string())
+    } else {
+        Ok(1)
+    }
+}
+
+// `fetch` is renamed inside `my_try!`
+#[maybe_async_cfg::maybe(transparent_macros(my_try), idents(fetch(fn), load(fn)), sync(), async())]
+async fn load(fail: bool) -> Result<u32, String> {
+    let value = my_try!(fetch(fail).await, "load");
+    Ok(value + 1)
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(load_sync(false), Ok(2));
+    assert_eq!(load_sync(true), Err("load: failed".to_string()));
+    assert_eq!(load_async(false).await, Ok(2));
+    assert_eq!(load_async(true).await, Err("load: failed".to_string()));
+}
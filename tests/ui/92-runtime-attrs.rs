@@ -0,0 +1,25 @@
+#![allow(dead_code)]
+
+// The sync version loses `#[tokio::main]` together with `async`
+#[maybe_async_cfg::maybe(idents(entry(fn)), sync(), async())]
+#[tokio::main]
+async fn entry() -> u32 {
+    1
+}
+
+#[maybe_async_cfg::maybe(idents(check(fn)), sync(test), async())]
+#[tokio::test]
+async fn check() {
+    assert_eq!(1 + 1, 2);
+}
+
+#[maybe_async_cfg::maybe(idents(check_std(fn)), sync(test), async())]
+#[async_std::test]
+async fn check_std() {
+    assert_eq!(1 + 1, 2);
+}
+
+fn main() {
+    assert_eq!(entry_sync(), 1);
+    assert_eq!(entry_async(), 1);
+}
@@ -0,0 +1,25 @@
+#![allow(dead_code)]
+
+// One struct, only its derives depend on the enabled version
+#[maybe_async_cfg::maybe(
+    conditional_attrs,
+    sync(feature = "is_sync", derive(Clone, PartialEq)),
+    async(not(feature = "is_sync"), derive(Clone, Debug, PartialEq)),
+)]
+#[derive(Default)]
+struct Data(u32);
+
+#[maybe_async_cfg::maybe(
+    conditional_attrs,
+    sync(feature = "is_sync", inline),
+    async(not(feature = "is_sync"), inline, must_use),
+)]
+fn double(value: u32) -> u32 {
+    value * 2
+}
+
+fn main() {
+    let data = Data::default();
+    assert_eq!(format!("{:?}", data.clone()), "Data(0)");
+    assert_eq!(double(2), 4);
+}
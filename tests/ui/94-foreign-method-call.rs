@@ -0,0 +1,22 @@
+#![allow(dead_code)]
+
+use std::io::Read;
+
+#[maybe_async_cfg::maybe(sync(), async())]
+async fn read() -> usize {
+    1
+}
+
+// `read` is renamed as a function, but `src.read(..)` calls `std::io::Read::read`
+#[maybe_async_cfg::maybe(idents(read(fn)), sync(), async())]
+async fn load(mut src: &[u8]) -> usize {
+    let mut buf = [0u8; 4];
+    let n = src.read(&mut buf).unwrap();
+    n + read().await
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(load_sync(b"ab"), 3);
+    assert_eq!(load_async(b"ab").await, 3);
+}
@@ -0,0 +1,35 @@
+#![allow(dead_code)]
+
+use std::future::Future;
+
+// Future generics of structs and impls are kept in the sync version, only functions are collapsed
+#[maybe_async_cfg::maybe(idents(Pending), sync(), async())]
+struct Pending<F: Future<Output = u32>> {
+    fut: F,
+}
+
+#[maybe_async_cfg::maybe(idents(Pending), sync(), async())]
+impl<F> Pending<F>
+where
+    F: Future<Output = u32>,
+{
+    fn into_inner(self) -> F {
+        self.fut
+    }
+
+    // generics of methods are collapsed as usual: `fn add_one(g: u32) -> u32`
+    async fn add_one<G: Future<Output = u32>>(g: G) -> u32 {
+        g.await + 1
+    }
+}
+
+#[async_std::main]
+async fn main() {
+    let pending = PendingSync { fut: std::future::ready(1) };
+    assert_eq!(async_std::task::block_on(pending.into_inner()), 1);
+    assert_eq!(PendingSync::<std::future::Ready<u32>>::add_one(1), 2);
+
+    let pending = PendingAsync { fut: std::future::ready(2) };
+    assert_eq!(pending.into_inner().await, 2);
+    assert_eq!(PendingAsync::<std::future::Ready<u32>>::add_one(std::future::ready(3)).await, 4);
+}
@@ -0,0 +1,37 @@
+#![allow(dead_code)]
+
+// tests/test.rs sets `MAYBE_ASYNC_CFG_REPORT`, the report is read back when the test runs
+#[maybe_async_cfg::maybe(idents(report_source(fn)), sync(), async())]
+async fn report_source() -> u32 {
+    1
+}
+
+#[maybe_async_cfg::maybe(idents(report_source(fn), report_target(fn)), sync(), async())]
+async fn report_target() -> u32 {
+    report_source().await + 1
+}
+
+fn main() {
+    assert_eq!(report_target_sync(), 2);
+
+    let path = std::env::var("MAYBE_ASYNC_CFG_REPORT").unwrap();
+    let report = std::fs::read_to_string(path).unwrap();
+    let line = |mode: &str| {
+        let mode = format!("\"mode\":\"{}\"", mode);
+        report
+            .lines()
+            .rev()
+            .find(|line| line.contains("\"item\":\"report_target\"") && line.contains(&mode))
+            .unwrap()
+            .to_string()
+    };
+
+    let sync = line("sync");
+    assert!(sync.contains("\"key\":\"sync\""));
+    assert!(sync.contains("{\"from\":\"report_source\",\"to\":\"report_source_sync\"}"));
+    assert!(sync.contains("\"awaits_stripped\":1"));
+
+    let async_ = line("async");
+    assert!(async_.contains("{\"from\":\"report_source\",\"to\":\"report_source_async\"}"));
+    assert!(async_.contains("\"awaits_stripped\":0"));
+}
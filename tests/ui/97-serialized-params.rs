@@ -0,0 +1,43 @@
+#![allow(dead_code, unexpected_cfgs)]
+
+// Top-level `replace_feature` and `drop_attrs` are written into the parameters of each version
+// and parsed back there, so all their forms must survive the round trip.
+#[maybe_async_cfg::maybe(
+    idents(Limits),
+    replace_feature("always", ""),
+    replace_feature("present", not(feature = "absent")),
+    replace_feature("excluded", "absent"),
+    sync(),
+    async(),
+)]
+struct Limits {
+    #[cfg(feature = "always")]
+    max: usize,
+    #[cfg(feature = "present")]
+    min: usize,
+    #[cfg(not(feature = "excluded"))]
+    step: usize,
+}
+
+#[maybe_async_cfg::maybe(idents(Sizes), drop_attrs(cfg), sync(), async())]
+struct Sizes {
+    #[cfg(any())]
+    len: usize,
+}
+
+#[maybe_async_cfg::maybe(idents(run(fn)), drop_attrs("tokio::*"), sync(), async())]
+#[tokio::main]
+async fn run() -> usize {
+    1
+}
+
+fn main() {
+    let _ = LimitsSync { max: 1, min: 0, step: 1 };
+    let _ = LimitsAsync { max: 1, min: 0, step: 1 };
+    let _ = SizesSync { len: 1 };
+    let _ = SizesAsync { len: 1 };
+
+    assert_eq!(run_sync(), 1);
+    // `#[tokio::main]` is dropped from the async version too, it is still an `async fn`
+    assert_eq!(async_std::task::block_on(run_async()), 1);
+}
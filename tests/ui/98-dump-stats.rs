@@ -0,0 +1,36 @@
+#![allow(dead_code)]
+
+#[maybe_async_cfg::maybe(idents(stats_source(fn)), sync(), async())]
+async fn stats_source() -> u32 {
+    1
+}
+
+// tests/test.rs sets `MAYBE_ASYNC_CFG_DUMP_DIR`, the dump is read back when the test runs
+#[maybe_async_cfg::maybe(
+    idents(stats_source(fn), stats_target(fn)),
+    drop_attrs(inline),
+    dump,
+    sync(),
+    async(),
+)]
+#[inline]
+async fn stats_target() -> u32 {
+    stats_source().await + stats_source().await
+}
+
+fn main() {
+    assert_eq!(stats_target_sync(), 2);
+
+    // `<item>.<mode>.<hash>.rs`
+    let dir = std::env::var("MAYBE_ASYNC_CFG_DUMP_DIR").unwrap();
+    let entry = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap())
+        .find(|entry| entry.file_name().to_string_lossy().starts_with("stats_target_sync.sync."))
+        .unwrap();
+    let dump = std::fs::read_to_string(entry.path()).unwrap();
+    assert!(dump.starts_with(
+        "// renames: 3, awaits stripped: 2, attributes dropped: 1, features replaced: 0\n"
+    ));
+    assert!(dump.contains("fn stats_target_sync"));
+}
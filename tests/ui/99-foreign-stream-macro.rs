@@ -0,0 +1,19 @@
+#![allow(dead_code)]
+
+// not `async_stream::stream!`: the body is not a list of statements
+macro_rules! stream {
+    ($from:expr => $to:expr) => {
+        ($from..$to).collect::<Vec<u32>>()
+    };
+}
+
+#[maybe_async_cfg::maybe(idents(range(fn)), sync(), async())]
+async fn range() -> Vec<u32> {
+    stream!(1 => 4)
+}
+
+#[async_std::main]
+async fn main() {
+    assert_eq!(range_sync(), vec![1, 2, 3]);
+    assert_eq!(range_async().await, vec![1, 2, 3]);
+}
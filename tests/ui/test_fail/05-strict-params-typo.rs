@@ -0,0 +1,12 @@
+#[maybe_async_cfg::maybe(
+    strict_params,
+    sync(diasble),
+    async(),
+)]
+async fn async_fn() -> bool {
+    true
+}
+
+fn main() {
+
+}
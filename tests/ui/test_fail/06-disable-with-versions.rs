@@ -0,0 +1,12 @@
+#[maybe_async_cfg::maybe(
+    disable,
+    sync(),
+    async(),
+)]
+async fn async_fn() -> bool {
+    true
+}
+
+fn main() {
+
+}
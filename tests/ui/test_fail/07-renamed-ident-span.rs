@@ -0,0 +1,15 @@
+pub struct ClientSync;
+
+#[maybe_async_cfg::maybe(
+    idents(Client, Missing(sync = "MissingSyncType"), Other, connect(fn)),
+    sync(),
+)]
+async fn connect() -> Client {
+    let _ = Missing;
+    let _ = Other;
+    Client
+}
+
+fn main() {
+
+}
@@ -0,0 +1,5 @@
+#[maybe_async_cfg::maybe(sync(), async())]
+#[maybe_async_cfg::raw(sync = "let x = 1;")]
+async fn foo() {}
+
+fn main() {}
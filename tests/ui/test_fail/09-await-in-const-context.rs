@@ -0,0 +1,11 @@
+#[maybe_async_cfg::maybe(sync(), async())]
+async fn size() -> usize {
+    4
+}
+
+#[maybe_async_cfg::maybe(sync(), async())]
+async fn buffer() -> [u8; 4] {
+    [0; size().await]
+}
+
+fn main() {}
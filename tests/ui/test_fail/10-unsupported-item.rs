@@ -0,0 +1,15 @@
+// With `keep_unsupported` the unsupported item is reported, but kept: `limits::LIMIT` can still
+// be used and the other items are converted
+#[maybe_async_cfg::maybe(keep_unsupported, sync())]
+mod limits {
+    pub const LIMIT: u32 = 2;
+}
+
+#[maybe_async_cfg::maybe(idents(doubled(fn)), sync())]
+async fn doubled() -> u32 {
+    limits::LIMIT * 2
+}
+
+fn main() {
+    assert_eq!(doubled_sync(), 4);
+}
@@ -0,0 +1,14 @@
+// `debug` and `no-debug` are the features of the test crate
+#[maybe_async_cfg::maybe(check_features, sync(feature = "debg"), async(feature = "no-debug"))]
+async fn read() -> u32 {
+    1
+}
+
+#[maybe_async_cfg::maybe(
+    check_features,
+    sync(any(feature = "debug", feature = "with_sync")),
+    async(not(feature = "debug")),
+)]
+async fn write() {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[maybe_async_cfg::maybe(
+    conditional_attrs,
+    sync(feature = "is_sync"),
+    async(not(feature = "is_sync")),
+)]
+async fn async_fn() -> bool {
+    true
+}
+
+fn main() {
+
+}
@@ -0,0 +1,7 @@
+// An unsupported item aborts the expansion by default
+#[maybe_async_cfg::maybe(sync())]
+mod limits {
+    pub const LIMIT: u32 = 2;
+}
+
+fn main() {}
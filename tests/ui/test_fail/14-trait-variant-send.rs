@@ -0,0 +1,19 @@
+use std::rc::Rc;
+
+// `#[trait_variant::make(Send)]` requires `Send` futures from the implementations
+#[maybe_async_cfg::maybe(idents(Store), async_trait = "trait_variant", async(send = "Send"))]
+trait Store {
+    async fn get(&self) -> u32;
+}
+
+struct Memory;
+
+impl StoreAsync for Memory {
+    async fn get(&self) -> u32 {
+        let value = Rc::new(1);
+        async {}.await;
+        *value
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#[maybe_async_cfg::maybe(sync(), async())]
+struct Store;
+
+// a `maybe` attribute on a method only changes the parameters, it can't define versions
+#[maybe_async_cfg::maybe(sync(), async())]
+impl Store {
+    #[maybe_async_cfg::maybe(idents(get(fn)), sync())]
+    async fn get(&self) -> u32 {
+        1
+    }
+}
+
+fn main() {}
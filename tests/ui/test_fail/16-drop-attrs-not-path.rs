@@ -0,0 +1,7 @@
+// string entries of `drop_attrs` must be attribute paths or path prefixes
+#[maybe_async_cfg::maybe(drop_attrs("not a path"), sync(), async())]
+async fn get() -> u32 {
+    1
+}
+
+fn main() {}